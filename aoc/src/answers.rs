@@ -0,0 +1,179 @@
+use crate::verdict::Verdict;
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// The local answers file, one tab-separated line per submitted answer:
+///
+/// ```text
+/// <day>\t<part>\t<answer>\t<verdict>
+/// ```
+///
+/// Answers can't contain tabs or line breaks, see [`is_recordable`].
+#[derive(Debug)]
+pub struct Answers {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Answers {
+    /// load the answers file, a missing file is just empty.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let entries = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                parse_entry(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}:{}: invalid entry: {}", path.display(), i + 1, line),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Answers { path, entries })
+    }
+
+    /// what we already know about this answer
+    pub fn lookup(&self, day: u8, part: u8, answer: &str) -> Option<&Verdict> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.part == part && e.answer == answer)
+            .map(|e| &e.verdict)
+    }
+
+    /// the correct answer for a part, if we already have one.
+    pub fn correct(&self, day: u8, part: u8) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.part == part && e.verdict == Verdict::Correct)
+            .map(|e| e.answer.as_str())
+    }
+
+    /// append a final verdict to the file.
+    pub fn record(&mut self, day: u8, part: u8, answer: &str, verdict: Verdict) -> io::Result<()> {
+        debug_assert!(verdict.is_final());
+        if !is_recordable(answer) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("answer {:?} contains a tab or line break", answer),
+            ));
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}\t{}\t{}\t{}", day, part, answer, verdict)?;
+
+        self.entries.push(Entry {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+        Ok(())
+    }
+}
+
+/// Whether the answer fits into a field of the answers file.
+pub fn is_recordable(answer: &str) -> bool {
+    !answer.contains(['\t', '\n', '\r'])
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut fields = line.split('\t');
+
+    let entry = Entry {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        answer: fields.next()?.to_string(),
+        verdict: fields.next()?.parse().ok()?,
+    };
+
+    if fields.next().is_some() {
+        return None;
+    }
+    Some(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let answers = Answers::load(dir.path().join("answers.txt")).unwrap();
+        assert!(answers.entries.is_empty());
+    }
+
+    #[test]
+    fn test_record_and_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.txt");
+
+        let mut answers = Answers::load(&path).unwrap();
+        answers.record(1, 1, "123", Verdict::TooLow).unwrap();
+        answers.record(1, 1, "24000", Verdict::Correct).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "1\t1\t123\ttoo-low\n1\t1\t24000\tcorrect\n"
+        );
+
+        let answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.lookup(1, 1, "123"), Some(&Verdict::TooLow));
+        assert_eq!(answers.lookup(1, 2, "123"), None);
+        assert_eq!(answers.correct(1, 1), Some("24000"));
+        assert_eq!(answers.correct(1, 2), None);
+    }
+
+    #[test]
+    fn test_answers_with_separators() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.txt");
+
+        let mut answers = Answers::load(&path).unwrap();
+        for answer in ["1\t2", "1\n2", "1\r"] {
+            let err = answers.record(1, 1, answer, Verdict::TooLow).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+        answers.record(1, 1, "1 2", Verdict::TooLow).unwrap();
+
+        let answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.entries.len(), 1);
+        assert_eq!(answers.lookup(1, 1, "1 2"), Some(&Verdict::TooLow));
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.txt");
+        std::fs::write(&path, "1\t1\t123\tcorrect\n1\tx\t123\tcorrect\n").unwrap();
+
+        let err = Answers::load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains(":2: invalid entry"));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::verdict::Verdict;
+use std::fmt;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum Error {
+    Status(u16),
+    Transport(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Status(code) => write!(f, "server responded with status {}", code),
+            Error::Transport(msg) => write!(f, "request failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ureq::Error> for Error {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(code, _) => Error::Status(code),
+            ureq::Error::Transport(transport) => Error::Transport(transport.to_string()),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>, year: u16) -> Self {
+        Client {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            year,
+            agent: ureq::agent(),
+        }
+    }
+
+    /// post the answer like the form on the puzzle page does, and parse the
+    /// page we get back.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, Error> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, self.year, day);
+
+        let page = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()
+            .map_err(|err| Error::Transport(err.to_string()))?;
+
+        Ok(Verdict::from_response(&page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// minimal stand-in for the AoC server: accepts a single request, sends it
+    /// back through the channel and answers with the given status and body.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, requests) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+
+        let client = Client::new(base_url + "/", "secret", 2022);
+        assert_eq!(client.submit(1, 2, "45000").unwrap(), Verdict::Correct);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2022/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+        assert!(request.contains("Content-Type: application/x-www-form-urlencoded\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=45000"));
+    }
+
+    #[test]
+    fn test_submit_wrong() {
+        let (base_url, _requests) = serve_once(
+            "200 OK",
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        );
+
+        let client = Client::new(base_url, "secret", 2022);
+        assert_eq!(client.submit(1, 1, "1").unwrap(), Verdict::TooLow);
+    }
+
+    #[test]
+    fn test_submit_status_error() {
+        let (base_url, _requests) = serve_once("400 Bad Request", "");
+
+        let client = Client::new(base_url, "secret", 2022);
+        assert!(matches!(
+            client.submit(1, 1, "1").unwrap_err(),
+            Error::Status(400)
+        ));
+    }
+}
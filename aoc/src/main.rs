@@ -0,0 +1,136 @@
+mod answers;
+mod client;
+mod verdict;
+
+use answers::Answers;
+use client::Client;
+use shared::store::{HttpFetcher, InputStore, Key};
+use std::io::BufRead;
+
+static USAGE: &str = "usage:
+    aoc submit <day> <part> [answer]
+    aoc fetch <day>
+
+submit reads the answer from stdin when it's not given.
+fetch prints the path of the cached input, downloading it when needed.
+
+environment:
+    AOC_SESSION   session cookie (required to submit or download)
+    AOC_BASE_URL  server to talk to (default: https://adventofcode.com)
+    AOC_YEAR      puzzle year (default: 2022)
+    AOC_ANSWERS   local answers file (default: answers.txt)
+    AOC_INPUTS    input cache directory (default: inputs)
+    AOC_ACCOUNT   name of the account in the input cache (default: default)";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["submit", day, part] => submit(day, part, &read_answer()),
+        ["submit", day, part, answer] => submit(day, part, answer),
+        ["fetch", day] => fetch(day),
+        _ => exit(USAGE),
+    }
+}
+
+fn exit(msg: impl std::fmt::Display) -> ! {
+    eprintln!("{}", msg);
+    std::process::exit(1);
+}
+
+fn read_answer() -> String {
+    let mut answer = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .unwrap_or_else(|err| exit(err));
+    answer.trim().to_string()
+}
+
+fn base_url() -> String {
+    std::env::var("AOC_BASE_URL").unwrap_or_else(|_| client::DEFAULT_BASE_URL.into())
+}
+
+fn year() -> u16 {
+    std::env::var("AOC_YEAR")
+        .map(|year| year.parse().unwrap_or_else(|_| exit("invalid AOC_YEAR")))
+        .unwrap_or(2022)
+}
+
+fn parse_day(day: &str) -> u8 {
+    day.parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .unwrap_or_else(|| exit(format!("invalid day: {}", day)))
+}
+
+fn fetch(day: &str) {
+    let day = parse_day(day);
+
+    let store = InputStore::new(std::env::var("AOC_INPUTS").unwrap_or_else(|_| "inputs".into()));
+    let account = std::env::var("AOC_ACCOUNT").unwrap_or_else(|_| "default".into());
+    let key = Key::new(year(), day, account.clone());
+
+    let fetcher = std::env::var("AOC_SESSION")
+        .ok()
+        .map(|session| HttpFetcher::new(base_url(), session, account));
+
+    store
+        .get(&key, fetcher.as_ref().map(|f| f as _))
+        .unwrap_or_else(|err| exit(err));
+
+    let path = store.path(&key).unwrap_or_else(|err| exit(err));
+    println!("{}", path.display());
+}
+
+fn submit(day: &str, part: &str, answer: &str) {
+    let day = parse_day(day);
+    let part: u8 = part
+        .parse()
+        .ok()
+        .filter(|part| (1..=2).contains(part))
+        .unwrap_or_else(|| exit(format!("invalid part: {}", part)));
+    if answer.is_empty() {
+        exit("empty answer");
+    }
+    // checked before submitting, so a verdict is never lost
+    if !answers::is_recordable(answer) {
+        exit("the answer contains a tab or line break");
+    }
+
+    let mut answers =
+        Answers::load(std::env::var("AOC_ANSWERS").unwrap_or_else(|_| "answers.txt".into()))
+            .unwrap_or_else(|err| exit(err));
+
+    if let Some(verdict) = answers.lookup(day, part, answer) {
+        println!("already submitted: {}", verdict);
+        return;
+    }
+    if let Some(correct) = answers.correct(day, part) {
+        println!("already solved, the answer was {}", correct);
+        return;
+    }
+
+    let client = Client::new(
+        base_url(),
+        std::env::var("AOC_SESSION").unwrap_or_else(|_| exit("AOC_SESSION is not set")),
+        year(),
+    );
+
+    let verdict = client
+        .submit(day, part, answer)
+        .unwrap_or_else(|err| exit(err));
+
+    println!("{}", verdict);
+
+    if verdict.is_final() {
+        answers
+            .record(day, part, answer, verdict)
+            .unwrap_or_else(|err| exit(err));
+    }
+}
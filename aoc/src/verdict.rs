@@ -0,0 +1,166 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// submitted too fast, the server tells us how long to wait (if it does)
+    Wait(Option<Duration>),
+    /// the part was already solved, or is not unlocked yet
+    WrongLevel,
+    Unknown(String),
+}
+
+impl Verdict {
+    /// parse the HTML page the server returns after posting an answer
+    pub fn from_response(page: &str) -> Self {
+        let text = article(page);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if text.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::Wait(wait_time(text))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown(text.trim().to_string())
+        }
+    }
+
+    /// only final verdicts are worth remembering, everything else can be retried.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+/// the interesting part of the page is in the first `<article>`,
+/// fall back to the whole page when there is none.
+fn article(page: &str) -> &str {
+    page.split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article)
+        .unwrap_or(page)
+}
+
+/// parses "You have 1m 5s left to wait" and "You have 30s left to wait"
+fn wait_time(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (amount, _) = rest.split_once(" left to wait")?;
+
+    let mut seconds = 0;
+    for part in amount.split_whitespace() {
+        if let Some(minutes) = part.strip_suffix('m') {
+            seconds += minutes.parse::<u64>().ok()? * 60;
+        } else if let Some(secs) = part.strip_suffix('s') {
+            seconds += secs.parse::<u64>().ok()?;
+        } else {
+            return None;
+        }
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wait(Some(duration)) => write!(f, "wait {}s", duration.as_secs()),
+            Verdict::Wait(None) => write!(f, "wait"),
+            Verdict::WrongLevel => write!(f, "wrong-level"),
+            Verdict::Unknown(text) => write!(f, "unknown: {}", text),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    /// only used for the final verdicts we store in the answers file
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            _ => Err(format!("unknown verdict: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    #[test_case(
+        "That's the right answer! You are one gold star closer to saving your vacation.",
+        Verdict::Correct
+    )]
+    #[test_case(
+        "That's not the right answer; your answer is too high. Please wait one minute.",
+        Verdict::TooHigh
+    )]
+    #[test_case(
+        "That's not the right answer; your answer is too low. Please wait one minute.",
+        Verdict::TooLow
+    )]
+    #[test_case(
+        "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+        Verdict::Wrong
+    )]
+    #[test_case(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait.",
+        Verdict::Wait(Some(Duration::from_secs(65)))
+    )]
+    #[test_case(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 30s left to wait.",
+        Verdict::Wait(Some(Duration::from_secs(30)))
+    )]
+    #[test_case("You gave an answer too recently.", Verdict::Wait(None))]
+    #[test_case(
+        "You don't seem to be solving the right level.  Did you already complete it?",
+        Verdict::WrongLevel
+    )]
+    fn test_from_response(article: &str, expected: Verdict) {
+        assert_eq!(Verdict::from_response(&page(article)), expected);
+    }
+
+    #[test]
+    fn test_unknown_only_contains_article() {
+        assert_eq!(
+            Verdict::from_response(&page("something else")),
+            Verdict::Unknown("<p>something else</p>".into())
+        );
+    }
+
+    #[test_case(Verdict::Correct)]
+    #[test_case(Verdict::Wrong)]
+    #[test_case(Verdict::TooHigh)]
+    #[test_case(Verdict::TooLow)]
+    fn test_roundtrip(verdict: Verdict) {
+        assert_eq!(verdict.to_string().parse::<Verdict>().unwrap(), verdict);
+    }
+}
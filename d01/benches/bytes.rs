@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use d01::{byte_sums, get_sums};
+
+static INPUT: &str = include_str!("../input.txt");
+
+fn bytes(c: &mut Criterion) {
+    // the puzzle input is small, repeat it to get to a few megabytes
+    let input = format!("{}\n\n", INPUT.trim_end()).repeat(200);
+
+    let mut group = c.benchmark_group("d01 sums");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    group.bench_function("get_sums", |b| {
+        b.iter(|| get_sums(black_box(&input).lines()))
+    });
+
+    group.bench_function("byte_sums", |b| {
+        b.iter(|| byte_sums(black_box(input.as_bytes())).collect::<Result<Vec<_>, _>>())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bytes);
+criterion_main!(benches);
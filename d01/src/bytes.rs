@@ -0,0 +1,175 @@
+use crate::{parse_line, ParseError};
+
+/// Iterator over the calorie sums of the elves, parsed straight from the
+/// raw input bytes, see [`byte_sums`].
+pub struct ByteSums<'a> {
+    input: &'a [u8],
+    line: usize,
+    current: Option<u64>,
+    failed: bool,
+}
+
+impl<'a> ByteSums<'a> {
+    /// The number of lines read so far.
+    pub(crate) fn line(&self) -> usize {
+        self.line
+    }
+
+    fn next_line(&mut self) -> Option<&'a [u8]> {
+        if self.input.is_empty() {
+            return None;
+        }
+
+        let line = match memchr::memchr(b'\n', self.input) {
+            Some(end) => {
+                let line = &self.input[..end];
+                self.input = &self.input[end + 1..];
+                line
+            }
+            None => std::mem::take(&mut self.input),
+        };
+        self.line += 1;
+
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+/// Parse a line consisting of nothing but digits.
+///
+/// All digits are checked at once at the end instead of branching on every
+/// byte. Anything else, including numbers with more digits than always fit
+/// into a `u64`, is left to the slow path.
+#[inline]
+fn parse_digits(line: &[u8]) -> Option<u64> {
+    if line.len() > 19 {
+        return None;
+    }
+
+    let mut value = 0u64;
+    let mut invalid = false;
+    for &byte in line {
+        let digit = byte.wrapping_sub(b'0');
+        invalid |= digit > 9;
+        value = value.wrapping_mul(10).wrapping_add(digit as u64);
+    }
+
+    (!invalid).then_some(value)
+}
+
+/// Parse everything that isn't plain digits, with the same results as
+/// [`parse_line`].
+#[cold]
+fn parse_slow(line: &[u8], number: usize) -> Result<Option<u64>, ParseError> {
+    match std::str::from_utf8(line) {
+        Ok(line) => parse_line(line, number),
+        Err(_) => Err(ParseError::InvalidNumber {
+            line: number,
+            content: String::from_utf8_lossy(line).trim().to_string(),
+        }),
+    }
+}
+
+impl Iterator for ByteSums<'_> {
+    type Item = Result<u64, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        while let Some(line) = self.next_line() {
+            let parsed = if line.is_empty() {
+                Ok(None)
+            } else if let Some(num) = parse_digits(line) {
+                Ok(Some(num))
+            } else {
+                parse_slow(line, self.line)
+            };
+
+            match parsed {
+                Ok(None) => {
+                    if let Some(sum) = self.current.replace(0) {
+                        return Some(Ok(sum));
+                    }
+                }
+                Ok(Some(num)) => match self.current.unwrap_or(0).checked_add(num) {
+                    Some(sum) => self.current = Some(sum),
+                    None => {
+                        self.failed = true;
+                        return Some(Err(ParseError::SumOverflow { line: self.line }));
+                    }
+                },
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        self.current.take().map(Ok)
+    }
+}
+
+/// Same as [`crate::sums`] over the lines of `input`, but working on the
+/// bytes directly, for inputs too large to be parsed line by line as
+/// strings.
+pub fn byte_sums(input: &[u8]) -> ByteSums<'_> {
+    ByteSums {
+        input,
+        line: 0,
+        current: None,
+        failed: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sums;
+    use test_case::test_case;
+
+    #[test_case("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000"; "example")]
+    #[test_case("1000\n2000\n"; "trailing newline")]
+    #[test_case("1\n\n\n2\n\n"; "empty elves")]
+    #[test_case("\n1\n"; "leading empty line")]
+    #[test_case(""; "empty")]
+    #[test_case("\n"; "only newline")]
+    #[test_case("1\r\n2\r\n\r\n3\r\n"; "crlf")]
+    #[test_case("1\n \t\n 2 \n+3\n"; "whitespace and sign")]
+    #[test_case("18446744073709551615\n"; "max")]
+    #[test_case("1\n18446744073709551615\n"; "sum overflow")]
+    #[test_case("99999999999999999999\n"; "number too large")]
+    #[test_case("00000000000000000000001\n"; "leading zeros")]
+    #[test_case("1\n\n12a4\n5\n"; "invalid number")]
+    #[test_case("1\n/\n"; "byte below digits")]
+    #[test_case("1\n:\n"; "byte above digits")]
+    fn test_same_as_sums(input: &str) {
+        assert_eq!(
+            byte_sums(input.as_bytes()).collect::<Vec<_>>(),
+            sums(input.lines()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        assert_eq!(
+            byte_sums(b"1\n\n2\xff\n").collect::<Vec<_>>(),
+            vec![
+                Ok(1),
+                Err(ParseError::InvalidNumber {
+                    line: 3,
+                    content: "2\u{fffd}".into()
+                })
+            ]
+        );
+    }
+
+    #[test_case(b"0", Some(0))]
+    #[test_case(b"1234567890", Some(1234567890))]
+    #[test_case(b"9999999999999999999", Some(9999999999999999999))]
+    #[test_case(b"10000000000000000000", None; "too many digits")]
+    #[test_case(b"12 ", None; "space")]
+    #[test_case(b"-1", None; "minus")]
+    fn test_parse_digits(line: &[u8], expected: Option<u64>) {
+        assert_eq!(parse_digits(line), expected);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{parse_line, top_n, ParseError};
+use std::ops::Range;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Elf {
+    /// position of the elf in the input, starting at 0
+    pub index: usize,
+    /// calories of every item the elf carries
+    pub items: Vec<u64>,
+    /// 0-based indices of the input lines with the items of the elf
+    pub lines: Range<usize>,
+}
+
+impl Elf {
+    pub fn total(&self) -> u64 {
+        self.items.iter().sum()
+    }
+}
+
+/// Parse the inventory, keeping track of which elf carries what.
+///
+/// Elves are split the same way as in [`crate::sums`], so
+/// `elves[i].total()` is the `i`-th sum.
+pub fn parse_elves<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> Result<Vec<Elf>, ParseError> {
+    let mut elves = Vec::new();
+    let mut current: Option<(Elf, u64)> = None;
+
+    for (i, line) in lines.enumerate() {
+        match parse_line(line.as_ref(), i + 1)? {
+            None => {
+                if let Some((elf, _)) = current.take() {
+                    elves.push(elf);
+                }
+                current = Some((
+                    Elf {
+                        index: elves.len(),
+                        items: Vec::new(),
+                        lines: i + 1..i + 1,
+                    },
+                    0,
+                ));
+            }
+            Some(calories) => {
+                let (elf, total) = current.get_or_insert_with(|| {
+                    (
+                        Elf {
+                            index: elves.len(),
+                            items: Vec::new(),
+                            lines: i..i,
+                        },
+                        0,
+                    )
+                });
+
+                // checking here means `Elf::total` can't overflow later
+                *total = total
+                    .checked_add(calories)
+                    .ok_or(ParseError::SumOverflow { line: i + 1 })?;
+
+                if elf.items.is_empty() {
+                    elf.lines.start = i;
+                }
+                elf.items.push(calories);
+                elf.lines.end = i + 1;
+            }
+        }
+    }
+
+    elves.extend(current.map(|(elf, _)| elf));
+    Ok(elves)
+}
+
+/// All elves carrying the most calories, in input order.
+pub fn strongest(elves: &[Elf]) -> Vec<&Elf> {
+    top_elves(elves, 1)
+}
+
+/// The elves carrying the `n` largest totals, most calories first and in
+/// input order for equal totals.
+///
+/// Elves tied with the `n`-th total are all included, so there can be more
+/// than `n` of them. Use [`Elf::total`] to see where they tie.
+pub fn top_elves(elves: &[Elf], n: usize) -> Vec<&Elf> {
+    let threshold = match top_n(elves.iter().map(Elf::total), n).last() {
+        Some(&threshold) => threshold,
+        None => return Vec::new(),
+    };
+
+    let mut top: Vec<&Elf> = elves.iter().filter(|e| e.total() >= threshold).collect();
+    top.sort_by_key(|elf| std::cmp::Reverse(elf.total()));
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    fn indices(elves: Vec<&Elf>) -> Vec<usize> {
+        elves.into_iter().map(|e| e.index).collect()
+    }
+
+    #[test]
+    fn test_parse_elves() {
+        let elves = parse_elves(TEST_INPUT.lines()).unwrap();
+
+        assert_eq!(elves.len(), 5);
+        assert_eq!(
+            elves[0],
+            Elf {
+                index: 0,
+                items: vec![1000, 2000, 3000],
+                lines: 0..3,
+            }
+        );
+        assert_eq!(
+            elves[3],
+            Elf {
+                index: 3,
+                items: vec![7000, 8000, 9000],
+                lines: 9..12,
+            }
+        );
+        assert_eq!(elves[4].lines, 13..14);
+        assert_eq!(
+            elves.iter().map(Elf::total).collect::<Vec<_>>(),
+            crate::get_sums(TEST_INPUT.lines()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_elves_empty_lines() {
+        let elves = parse_elves("\n1\n\n\n2\n".lines()).unwrap();
+
+        assert_eq!(elves.len(), 3);
+        assert_eq!(elves[0].lines, 1..2);
+        assert_eq!(elves[1].items, vec![]);
+        assert_eq!(elves[1].lines, 3..3);
+        assert_eq!(elves[2].lines, 4..5);
+    }
+
+    #[test]
+    fn test_strongest() {
+        let elves = parse_elves(TEST_INPUT.lines()).unwrap();
+        assert_eq!(indices(strongest(&elves)), vec![3]);
+    }
+
+    #[test]
+    fn test_strongest_tie() {
+        let elves = parse_elves("5\n\n3\n2\n\n1".lines()).unwrap();
+        assert_eq!(indices(strongest(&elves)), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_top_elves() {
+        let elves = parse_elves(TEST_INPUT.lines()).unwrap();
+        assert_eq!(indices(top_elves(&elves, 3)), vec![3, 2, 4]);
+        assert_eq!(indices(top_elves(&elves, 0)), vec![]);
+        assert_eq!(indices(top_elves(&elves, 10)), vec![3, 2, 4, 0, 1]);
+    }
+
+    #[test]
+    fn test_top_elves_tie_at_the_end() {
+        let elves = parse_elves("9\n\n5\n\n4\n1\n\n2".lines()).unwrap();
+        assert_eq!(indices(top_elves(&elves, 2)), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_parse_elves_errors() {
+        assert_eq!(
+            parse_elves("1\n\n2\nx".lines()),
+            Err(ParseError::InvalidNumber {
+                line: 4,
+                content: "x".into()
+            })
+        );
+        assert_eq!(
+            parse_elves(format!("1\n{}", u64::MAX).lines()),
+            Err(ParseError::SumOverflow { line: 2 })
+        );
+    }
+}
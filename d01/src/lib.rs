@@ -0,0 +1,232 @@
+mod bytes;
+mod elf;
+mod parse;
+mod planner;
+mod stats;
+mod top;
+mod tracker;
+
+pub use bytes::{byte_sums, ByteSums};
+pub use elf::{parse_elves, strongest, top_elves, Elf};
+pub use parse::{parse_line, ParseError};
+pub use planner::{plan, Pick, PickMode, Plan};
+pub use stats::Stats;
+pub use top::top_n;
+pub use tracker::CalorieTracker;
+
+/// Iterator over the calorie sums of the elves, computed while reading the
+/// lines, see [`sums`].
+pub struct Sums<I> {
+    lines: I,
+    line: usize,
+    current: Option<u64>,
+    failed: bool,
+}
+
+impl<I, T> Iterator for Sums<I>
+where
+    I: Iterator<Item = T>,
+    T: AsRef<str>,
+{
+    type Item = Result<u64, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        for line in self.lines.by_ref() {
+            self.line += 1;
+
+            match parse_line(line.as_ref(), self.line) {
+                Ok(None) => {
+                    if let Some(sum) = self.current.replace(0) {
+                        return Some(Ok(sum));
+                    }
+                }
+                Ok(Some(num)) => match self.current.unwrap_or(0).checked_add(num) {
+                    Some(sum) => self.current = Some(sum),
+                    None => {
+                        self.failed = true;
+                        return Some(Err(ParseError::SumOverflow { line: self.line }));
+                    }
+                },
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        self.current.take().map(Ok)
+    }
+}
+
+/// The calorie sum of every elf, without keeping them all in memory.
+///
+/// Stops after the first error.
+pub fn sums<T: AsRef<str>, I: Iterator<Item = T>>(lines: I) -> Sums<I> {
+    Sums {
+        lines,
+        line: 0,
+        current: None,
+        failed: false,
+    }
+}
+
+pub fn get_sums<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> Result<Vec<u64>, ParseError> {
+    sums(lines).collect()
+}
+
+pub fn part_1<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> Result<u64, ParseError> {
+    max_sum(sums(lines))
+}
+
+pub fn part_2<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> Result<u64, ParseError> {
+    let mut sums = sums(lines);
+    top_3_total(&mut sums)?.ok_or(ParseError::SumOverflow { line: sums.line })
+}
+
+/// [`part_1`] on the raw input, see [`byte_sums`].
+pub fn part_1_bytes(input: &[u8]) -> Result<u64, ParseError> {
+    max_sum(byte_sums(input))
+}
+
+/// [`part_2`] on the raw input, see [`byte_sums`].
+pub fn part_2_bytes(input: &[u8]) -> Result<u64, ParseError> {
+    let mut sums = byte_sums(input);
+    top_3_total(&mut sums)?.ok_or(ParseError::SumOverflow { line: sums.line() })
+}
+
+fn max_sum(sums: impl Iterator<Item = Result<u64, ParseError>>) -> Result<u64, ParseError> {
+    let mut max = None;
+    for sum in sums {
+        max = max.max(Some(sum?));
+    }
+    max.ok_or(ParseError::NoElves)
+}
+
+/// The total of the three largest sums, `None` if it doesn't fit into a
+/// `u64`, which is only known after the last line.
+fn top_3_total(
+    sums: impl Iterator<Item = Result<u64, ParseError>>,
+) -> Result<Option<u64>, ParseError> {
+    let mut error = None;
+    let top = top_n(
+        sums.map_while(|sum| sum.map_err(|err| error = Some(err)).ok()),
+        3,
+    );
+
+    match error {
+        Some(err) => Err(err),
+        None if top.is_empty() => Err(ParseError::NoElves),
+        None => Ok(top
+            .into_iter()
+            .try_fold(0u64, |total, sum| total.checked_add(sum))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = "
+        1000
+        2000
+        3000
+
+        4000
+
+        5000
+        6000
+
+        7000
+        8000
+        9000
+
+        10000";
+
+    #[test]
+    fn test_1() {
+        assert_eq!(part_1(TEST_INPUT.lines()), Ok(24000))
+    }
+
+    #[test]
+    fn test_2() {
+        assert_eq!(part_2(TEST_INPUT.lines()), Ok(45000))
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(part_1_bytes(TEST_INPUT.as_bytes()), Ok(24000));
+        assert_eq!(part_2_bytes(TEST_INPUT.as_bytes()), Ok(45000));
+    }
+
+    #[test]
+    fn test_get_sums() {
+        assert_eq!(
+            get_sums(TEST_INPUT.lines()),
+            Ok(vec![6000, 4000, 11000, 24000, 10000])
+        );
+        // a trailing empty line starts an elf without any items
+        assert_eq!(get_sums("1\n2\n\n3\n\n".lines()), Ok(vec![3, 3, 0]));
+    }
+
+    #[test]
+    fn test_crlf_and_whitespace_separators() {
+        assert_eq!(
+            get_sums("1\r\n2\r\n\r\n3\r\n \t\r\n4".split('\n')),
+            Ok(vec![3, 3, 4])
+        );
+    }
+
+    #[test]
+    fn test_invalid_number() {
+        let input = "1000\n\n20x0\n3000";
+        let expected = Err(ParseError::InvalidNumber {
+            line: 3,
+            content: "20x0".into(),
+        });
+
+        assert_eq!(part_1(input.lines()), expected);
+        assert_eq!(part_2(input.lines()), expected);
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        let input = format!("1\n\n{}\n1\n", u64::MAX);
+
+        assert_eq!(
+            part_1(input.lines()),
+            Err(ParseError::SumOverflow { line: 4 })
+        );
+    }
+
+    #[test]
+    fn test_top_3_overflow() {
+        // every elf fits, but not the three of them together
+        let half = u64::MAX / 2;
+        let input = format!("{}\n\n{}\n\n{}\n\n1", half, half, half);
+        let expected = Err(ParseError::SumOverflow { line: 7 });
+
+        assert_eq!(part_1(input.lines()), Ok(half));
+        assert_eq!(part_2(input.lines()), expected);
+        assert_eq!(part_2_bytes(input.as_bytes()), expected);
+    }
+
+    #[test]
+    fn test_no_elves() {
+        assert_eq!(part_1("".lines()), Err(ParseError::NoElves));
+        assert_eq!(part_1_bytes(b""), Err(ParseError::NoElves));
+        assert_eq!(part_2("".lines()), Err(ParseError::NoElves));
+        assert_eq!(part_2_bytes(b""), Err(ParseError::NoElves));
+    }
+
+    #[test]
+    fn test_stops_after_error() {
+        let mut sums = sums("1\n\nx\n\n2".lines());
+
+        assert_eq!(sums.next(), Some(Ok(1)));
+        assert!(matches!(sums.next(), Some(Err(_))));
+        assert_eq!(sums.next(), None);
+    }
+}
@@ -1,77 +1,95 @@
+use d01::{
+    parse_elves, part_1_bytes, part_2_bytes, plan, CalorieTracker, ParseError, PickMode, Stats,
+};
+use shared::input::Mapped;
+use std::io::BufRead;
+
 fn main() {
-    let lines: Vec<String> = std::fs::read_to_string(std::env::args().nth(1).unwrap())
-        .unwrap()
-        .lines()
-        .map(|l| l.to_string())
-        .collect();
+    // `-` follows an endless inventory on stdin instead of solving a file
+    let result = if std::env::args().nth(1).as_deref() == Some("-") {
+        follow(std::io::stdin().lock())
+    } else {
+        let input = shared::input::map();
+        solve(&input)
+    };
 
-    println!("day 1: {}", part_1(lines.iter()));
-    println!("day 2: {}", part_2(lines.iter()));
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
 }
 
-fn get_sums<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> Vec<u64> {
-    lines
-        .map(|line| {
-            let line = line.as_ref().trim();
+const USAGE: &str = "usage: d01 <input> [--stats | --plan <target> [--one-per-elf]] | d01 -";
 
-            if line.is_empty() {
-                None
-            } else {
-                Some(line.parse::<u64>().unwrap())
-            }
-        })
-        .fold(Vec::new(), |mut sums, num| {
-            if let Some(num) = num {
-                if sums.is_empty() {
-                    sums.push(num);
-                } else {
-                    (*sums.last_mut().unwrap()) += num;
-                }
-            } else {
-                sums.push(0);
-            }
-            sums
-        })
+fn usage(problem: &str) -> ! {
+    eprintln!("{}\n{}", problem, USAGE);
+    std::process::exit(2);
 }
 
-fn part_1<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> u64 {
-    let sums = get_sums(lines);
-    sums.into_iter().max().expect("no max")
-}
-
-fn part_2<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> u64 {
-    let mut sums = get_sums(lines);
-    sums.sort();
-    sums.iter().rev().take(3).sum::<u64>()
-}
+fn solve(mapped: &Mapped) -> Result<(), ParseError> {
+    let args: Vec<String> = std::env::args().skip(2).collect();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["--stats"] => {
+            let input = mapped.as_str().expect("input is not utf-8");
+            let stats = Stats::new(&parse_elves(input.lines())?).ok_or(ParseError::NoElves)?;
+            print!("{}", stats);
+            return Ok(());
+        }
+        ["--plan", target, mode @ ..] => {
+            let target = target
+                .parse()
+                .unwrap_or_else(|_| usage(&format!("invalid target {:?}", target)));
+            let mode = match mode {
+                [] => PickMode::AnyItems,
+                ["--one-per-elf"] => PickMode::OnePerElf,
+                _ => usage(&format!("unknown plan mode {:?}", mode.join(" "))),
+            };
 
-    static TEST_INPUT: &str = "
-        1000
-        2000
-        3000
-
-        4000
-
-        5000
-        6000
+            let input = mapped.as_str().expect("input is not utf-8");
+            let elves = parse_elves(input.lines())?;
+            let plan = plan(&elves, target, mode);
+            for pick in &plan.picks {
+                println!(
+                    "elf {} (line {}): {}",
+                    pick.elf,
+                    elves[pick.elf].lines.start + pick.item + 1,
+                    pick.calories
+                );
+            }
+            println!("total: {} (target {})", plan.total, target);
+            return Ok(());
+        }
+        _ => {}
+    }
 
-        7000
-        8000
-        9000
+    // the plain answers don't need the input as a string, which saves
+    // checking all of a huge input for valid utf-8 first
+    println!("day 1: {}", part_1_bytes(mapped.as_bytes())?);
+    println!("day 2: {}", part_2_bytes(mapped.as_bytes())?);
+    Ok(())
+}
 
-        10000";
+fn follow(input: impl BufRead) -> Result<(), ParseError> {
+    let mut tracker = CalorieTracker::new();
 
-    #[test]
-    fn test_1() {
-        assert_eq!(part_1(TEST_INPUT.lines()), 24000)
-    }
+    for line in input.lines() {
+        let line = line.expect("could not read from stdin");
+        tracker.push_line(&line)?;
 
-    #[test]
-    fn test_2() {
-        assert_eq!(part_2(TEST_INPUT.lines()), 45000)
+        if line.trim().is_empty() {
+            println!(
+                "elves: {}, max: {}, top 3: {}",
+                tracker.elves() - 1,
+                tracker.max().unwrap_or(0),
+                tracker.top(3).iter().sum::<u64>()
+            );
+        }
     }
+    Ok(())
 }
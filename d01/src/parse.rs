@@ -0,0 +1,119 @@
+use std::{fmt, num::IntErrorKind};
+
+/// Line numbers start at 1.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    InvalidNumber {
+        line: usize,
+        content: String,
+    },
+    NumberTooLarge {
+        line: usize,
+        content: String,
+    },
+    /// the calories of the elf ending at this line don't fit into a `u64`,
+    /// or those of the top three elves of the input ending there
+    SumOverflow {
+        line: usize,
+    },
+    /// the input has no items at all
+    NoElves,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidNumber { line, content } => {
+                write!(f, "line {}: invalid number {:?}", line, content)
+            }
+            ParseError::NumberTooLarge { line, content } => {
+                write!(f, "line {}: number too large {:?}", line, content)
+            }
+            ParseError::SumOverflow { line } => {
+                write!(f, "line {}: calorie sum overflows", line)
+            }
+            ParseError::NoElves => write!(f, "no elves in the input"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse a single input line, `None` for the lines separating the elves.
+///
+/// Surrounding whitespace is ignored, which includes the `\r` of CRLF line
+/// endings when the lines were split on `\n` only. Lines containing only
+/// whitespace count as separators.
+pub fn parse_line(line: &str, number: usize) -> Result<Option<u64>, ParseError> {
+    let line = line.trim();
+
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    line.parse::<u64>()
+        .map(Some)
+        .map_err(|err| match err.kind() {
+            IntErrorKind::PosOverflow => ParseError::NumberTooLarge {
+                line: number,
+                content: line.to_string(),
+            },
+            _ => ParseError::InvalidNumber {
+                line: number,
+                content: line.to_string(),
+            },
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("1000", Some(1000); "number")]
+    #[test_case("  1000\t", Some(1000); "surrounding whitespace")]
+    #[test_case("1000\r", Some(1000); "carriage return")]
+    #[test_case("", None; "empty")]
+    #[test_case("\r", None; "only carriage return")]
+    #[test_case(" \t ", None; "only whitespace")]
+    #[test_case("18446744073709551615", Some(u64::MAX); "max")]
+    fn test_parse_line(line: &str, expected: Option<u64>) {
+        assert_eq!(parse_line(line, 1), Ok(expected));
+    }
+
+    #[test_case("10a0"; "letter")]
+    #[test_case("-1"; "negative")]
+    #[test_case("1 000"; "inner whitespace")]
+    fn test_parse_line_invalid(line: &str) {
+        assert_eq!(
+            parse_line(line, 7),
+            Err(ParseError::InvalidNumber {
+                line: 7,
+                content: line.into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_line_too_large() {
+        assert_eq!(
+            parse_line(" 18446744073709551616", 3),
+            Err(ParseError::NumberTooLarge {
+                line: 3,
+                content: "18446744073709551616".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ParseError::InvalidNumber {
+                line: 2,
+                content: "x".into()
+            }
+            .to_string(),
+            "line 2: invalid number \"x\""
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use crate::Elf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickMode {
+    /// any subset of all items
+    AnyItems,
+    /// at most one item from every elf
+    OnePerElf,
+}
+
+/// An item taken from an elf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pick {
+    /// index of the elf
+    pub elf: usize,
+    /// index of the item in the inventory of the elf
+    pub item: usize,
+    pub calories: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub picks: Vec<Pick>,
+    pub total: u64,
+}
+
+impl Plan {
+    pub fn is_exact(&self, target: u64) -> bool {
+        self.total == target
+    }
+}
+
+/// Marks totals that aren't reachable in the back-pointers of [`plan`].
+const NO_PICK: u32 = u32::MAX;
+
+/// Pick items whose calories add up to `target`, or get as close as
+/// possible to it. When the closest totals below and above the target are
+/// equally far away, the one below wins.
+///
+/// This is a subset sum over the reachable totals up to `target` plus the
+/// largest item, or twice the target if that's less (nothing above either
+/// can be closer), kept as a bitset. Every
+/// reachable total also keeps the index of the item that first reached it,
+/// to walk back from the result. It needs
+/// `O(items * (target + largest item) / 64)` time and 4 bytes and a bit of
+/// memory for every total up to `target + largest item`.
+pub fn plan(elves: &[Elf], target: u64, mode: PickMode) -> Plan {
+    let mut picks = Vec::new();
+    // the range of the picks of every elf
+    let mut groups = Vec::with_capacity(elves.len());
+    for elf in elves {
+        let start = picks.len();
+        picks.extend(
+            elf.items
+                .iter()
+                .enumerate()
+                .filter(|(_, &calories)| calories > 0)
+                .map(|(item, &calories)| Pick {
+                    elf: elf.index,
+                    item,
+                    calories,
+                }),
+        );
+        groups.push(start..picks.len());
+    }
+    assert!(picks.len() < NO_PICK as usize, "too many items");
+
+    // only totals up to the bound matter, so larger ones may saturate
+    let reachable_total = groups
+        .iter()
+        .map(|group| {
+            let calories = picks[group.clone()].iter().map(|p| p.calories);
+            match mode {
+                PickMode::AnyItems => calories.fold(0, u64::saturating_add),
+                PickMode::OnePerElf => calories.max().unwrap_or(0),
+            }
+        })
+        .fold(0, u64::saturating_add);
+    let largest = picks.iter().map(|p| p.calories).max().unwrap_or(0);
+    // an empty plan is `target` away, so totals from twice the target on
+    // can't be closer
+    let bound = target
+        .saturating_add(largest)
+        .min(target.saturating_mul(2))
+        .min(reachable_total) as usize;
+
+    let mut reachable = BitSet::new(bound + 1);
+    reachable.insert(0);
+    // the index of the pick that first made a total reachable
+    let mut via = vec![NO_PICK; bound + 1];
+
+    for group in groups {
+        // with one item per elf, every item of the elf can only extend
+        // totals without any item of this elf
+        let before = match mode {
+            PickMode::AnyItems => None,
+            PickMode::OnePerElf => Some(reachable.clone()),
+        };
+        for index in group {
+            reachable.add_shifted(before.as_ref(), picks[index].calories as usize, |total| {
+                via[total] = index as u32
+            });
+        }
+    }
+
+    let target = target.min(bound as u64) as usize;
+    let below = (0..=target).rev().find(|&t| reachable.contains(t));
+    let above = (target + 1..=bound).find(|&t| reachable.contains(t));
+
+    let mut total = match (below, above) {
+        (Some(below), Some(above)) if above - target < target - below => above,
+        (Some(below), _) => below,
+        (None, _) => unreachable!("0 is always reachable"),
+    };
+
+    let mut path = Vec::new();
+    let result = total as u64;
+    while total > 0 {
+        debug_assert_ne!(via[total], NO_PICK, "reachable total without pick");
+        let pick = picks[via[total] as usize];
+        total -= pick.calories as usize;
+        path.push(pick);
+    }
+    path.reverse();
+
+    Plan {
+        picks: path,
+        total: result,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        BitSet {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// `self |= source << shift`, where no `source` means `self`, calling
+    /// `added` for every newly set bit.
+    fn add_shifted(&mut self, source: Option<&BitSet>, shift: usize, mut added: impl FnMut(usize)) {
+        let (word_shift, bit_shift) = (shift / 64, shift % 64);
+
+        // from the top, so shifting `self` only reads words it didn't change yet
+        for i in (word_shift..self.words.len()).rev() {
+            let word = |j: usize| source.map_or(self.words[j], |source| source.words[j]);
+
+            let mut shifted = word(i - word_shift) << bit_shift;
+            if bit_shift > 0 && i > word_shift {
+                shifted |= word(i - word_shift - 1) >> (64 - bit_shift);
+            }
+            if i == self.words.len() - 1 && !self.len.is_multiple_of(64) {
+                shifted &= (1 << (self.len % 64)) - 1;
+            }
+
+            let mut new = shifted & !self.words[i];
+            self.words[i] |= new;
+
+            while new != 0 {
+                added(i * 64 + new.trailing_zeros() as usize);
+                new &= new - 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_elves;
+    use test_case::test_case;
+
+    static TEST_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    fn check(elves: &[Elf], plan: &Plan, mode: PickMode) {
+        assert_eq!(
+            plan.picks.iter().map(|p| p.calories).sum::<u64>(),
+            plan.total
+        );
+
+        for pick in &plan.picks {
+            assert_eq!(elves[pick.elf].items[pick.item], pick.calories);
+        }
+
+        let mut used: Vec<_> = plan
+            .picks
+            .iter()
+            .map(|p| match mode {
+                PickMode::AnyItems => (p.elf, p.item),
+                PickMode::OnePerElf => (p.elf, 0),
+            })
+            .collect();
+        used.sort();
+        used.dedup();
+        assert_eq!(used.len(), plan.picks.len(), "item or elf used twice");
+    }
+
+    #[test_case(11000, PickMode::AnyItems, 11000)]
+    #[test_case(55000, PickMode::AnyItems, 55000)]
+    #[test_case(60000, PickMode::AnyItems, 55000; "more than everything")]
+    #[test_case(0, PickMode::AnyItems, 0)]
+    #[test_case(500, PickMode::AnyItems, 0; "tie goes below")]
+    #[test_case(1400, PickMode::AnyItems, 1000)]
+    #[test_case(1600, PickMode::AnyItems, 2000)]
+    #[test_case(3000, PickMode::OnePerElf, 3000)]
+    #[test_case(33000, PickMode::OnePerElf, 32000; "one per elf is limited")]
+    #[test_case(20500, PickMode::OnePerElf, 20000)]
+    fn test_plan(target: u64, mode: PickMode, expected: u64) {
+        let elves = parse_elves(TEST_INPUT.lines()).unwrap();
+
+        let plan = plan(&elves, target, mode);
+
+        check(&elves, &plan, mode);
+        assert_eq!(plan.total, expected);
+    }
+
+    #[test]
+    fn test_one_per_elf_needs_other_items() {
+        // 3 + 3 from one elf would be exact, but only one item per elf is allowed
+        let elves = parse_elves("3\n3\n\n1".lines()).unwrap();
+
+        let plan = plan(&elves, 6, PickMode::OnePerElf);
+        assert_eq!(plan.total, 4);
+        check(&elves, &plan, PickMode::OnePerElf);
+
+        let plan = super::plan(&elves, 6, PickMode::AnyItems);
+        assert!(plan.is_exact(6));
+    }
+
+    #[test]
+    fn test_huge_items() {
+        // all items together don't fit into a `u64`
+        let half = u64::MAX / 2;
+        let input = format!("{}\n{}\n\n{}\n\n5\n7", half, half, half);
+        let elves = parse_elves(input.lines()).unwrap();
+
+        for mode in [PickMode::AnyItems, PickMode::OnePerElf] {
+            let plan = plan(&elves, 12, mode);
+            check(&elves, &plan, mode);
+            assert_eq!(plan.total, if mode == PickMode::AnyItems { 12 } else { 7 });
+        }
+    }
+
+    #[test]
+    fn test_many_items() {
+        // 2000 pseudo random items in 200 elves
+        let mut seed = 42u64;
+        let input: String = (0..2000)
+            .map(|i| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                let calories = 1000 + (seed >> 33) % 4000;
+                if i % 10 == 9 {
+                    format!("{}\n\n", calories)
+                } else {
+                    format!("{}\n", calories)
+                }
+            })
+            .collect();
+        let elves = parse_elves(input.lines()).unwrap();
+        assert_eq!(elves.len(), 201);
+
+        // the first item of every other elf, so an exact solution exists
+        let target: u64 = elves
+            .iter()
+            .step_by(2)
+            .filter_map(|e| e.items.first())
+            .sum();
+
+        for mode in [PickMode::AnyItems, PickMode::OnePerElf] {
+            let plan = plan(&elves, target, mode);
+            check(&elves, &plan, mode);
+            assert!(plan.is_exact(target));
+        }
+    }
+}
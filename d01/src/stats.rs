@@ -0,0 +1,249 @@
+use crate::Elf;
+use std::{collections::BTreeMap, fmt};
+
+const HISTOGRAM_BUCKETS: usize = 10;
+const HISTOGRAM_WIDTH: usize = 50;
+
+/// Statistics about the calories the elves carry.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    /// totals of all elves, sorted ascending
+    totals: Vec<u64>,
+    pub mean: f64,
+    /// population standard deviation
+    pub std_dev: f64,
+    /// number of items carried -> number of elves carrying that many
+    pub item_counts: BTreeMap<usize, usize>,
+}
+
+impl Stats {
+    /// `None` when there are no elves.
+    pub fn new(elves: &[Elf]) -> Option<Self> {
+        if elves.is_empty() {
+            return None;
+        }
+
+        let mut totals: Vec<u64> = elves.iter().map(Elf::total).collect();
+        totals.sort_unstable();
+
+        let count = totals.len() as f64;
+        let mean = totals.iter().map(|&t| t as f64).sum::<f64>() / count;
+        let variance = totals
+            .iter()
+            .map(|&t| (t as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        let mut item_counts = BTreeMap::new();
+        for elf in elves {
+            *item_counts.entry(elf.items.len()).or_default() += 1;
+        }
+
+        Some(Stats {
+            totals,
+            mean,
+            std_dev: variance.sqrt(),
+            item_counts,
+        })
+    }
+
+    pub fn count(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn min(&self) -> u64 {
+        self.totals[0]
+    }
+
+    pub fn max(&self) -> u64 {
+        self.totals[self.totals.len() - 1]
+    }
+
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// The `p`-th percentile (`0..=100`) of the totals, linearly
+    /// interpolated between the closest ranks.
+    pub fn percentile(&self, p: f64) -> f64 {
+        assert!((0.0..=100.0).contains(&p), "invalid percentile {}", p);
+
+        let rank = p / 100.0 * (self.totals.len() - 1) as f64;
+        let lower = self.totals[rank.floor() as usize] as f64;
+        let upper = self.totals[rank.ceil() as usize] as f64;
+
+        lower + (upper - lower) * rank.fract()
+    }
+
+    /// Number of elves per bucket, for `buckets` equally wide ranges between
+    /// the smallest and the largest total. Returns the start of each bucket
+    /// and its count. There are fewer buckets when they would start past
+    /// the largest total.
+    pub fn buckets(&self, buckets: usize) -> Vec<(u64, usize)> {
+        assert!(buckets > 0);
+
+        let range = self.max() - self.min();
+        // rounded up, so the buckets reach the largest total
+        let width = range.div_ceil(buckets as u64).max(1);
+        let buckets = (buckets as u64).min((range / width).saturating_add(1)) as usize;
+        let mut result: Vec<(u64, usize)> = (0..buckets as u64)
+            .map(|i| (self.min() + i * width, 0))
+            .collect();
+
+        for &total in &self.totals {
+            let bucket = (((total - self.min()) / width) as usize).min(buckets - 1);
+            result[bucket].1 += 1;
+        }
+        result
+    }
+
+    /// text histogram of the totals, bars scaled to at most `width` chars.
+    pub fn histogram(&self, buckets: usize, width: usize) -> String {
+        let buckets = self.buckets(buckets);
+        // there's always at least one elf, so this is never 0
+        let largest = buckets.iter().map(|&(_, count)| count).max().unwrap();
+        let label_width = self.max().to_string().len();
+
+        buckets
+            .iter()
+            .map(|&(start, count)| {
+                format!(
+                    "{:>label_width$} | {:<width$} {}\n",
+                    start,
+                    "#".repeat(count * width / largest),
+                    count,
+                    label_width = label_width,
+                    width = width,
+                )
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves:    {}", self.count())?;
+        writeln!(f, "min:      {}", self.min())?;
+        writeln!(f, "max:      {}", self.max())?;
+        writeln!(f, "mean:     {:.1}", self.mean)?;
+        writeln!(f, "median:   {:.1}", self.median())?;
+        writeln!(f, "std dev:  {:.1}", self.std_dev)?;
+        for p in [10, 25, 75, 90, 99] {
+            writeln!(f, "p{:<2}:      {:.1}", p, self.percentile(p as f64))?;
+        }
+
+        writeln!(f, "\nitems per elf:")?;
+        for (items, elves) in &self.item_counts {
+            writeln!(f, "{:>4} items: {} elves", items, elves)?;
+        }
+
+        writeln!(f, "\ncalories per elf:")?;
+        write!(f, "{}", self.histogram(HISTOGRAM_BUCKETS, HISTOGRAM_WIDTH))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_elves;
+
+    static TEST_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    fn stats() -> Stats {
+        Stats::new(&parse_elves(TEST_INPUT.lines()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(Stats::new(&[]), None);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = stats();
+
+        assert_eq!(stats.count(), 5);
+        assert_eq!(stats.min(), 4000);
+        assert_eq!(stats.max(), 24000);
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median(), 10000.0);
+        assert!((stats.std_dev - 6985.700).abs() < 0.001);
+        assert_eq!(stats.item_counts, BTreeMap::from([(1, 2), (2, 1), (3, 2)]));
+    }
+
+    #[test]
+    fn test_percentile() {
+        let stats = stats();
+
+        assert_eq!(stats.percentile(0.0), 4000.0);
+        assert_eq!(stats.percentile(25.0), 6000.0);
+        assert_eq!(stats.percentile(100.0), 24000.0);
+        // halfway between 11000 and 24000
+        assert_eq!(stats.percentile(87.5), 17500.0);
+    }
+
+    #[test]
+    fn test_buckets() {
+        assert_eq!(
+            stats().buckets(4),
+            vec![(4000, 2), (9000, 2), (14000, 0), (19000, 1)]
+        );
+    }
+
+    #[test]
+    fn test_uneven_buckets() {
+        // totals 1 to 20, a range of 19 in 10 buckets of 2
+        let input: Vec<String> = (1..=20).map(|total| total.to_string()).collect();
+        let input = input.join("\n\n");
+        let stats = Stats::new(&parse_elves(input.lines()).unwrap()).unwrap();
+
+        let buckets = stats.buckets(10);
+        assert_eq!(buckets.first(), Some(&(1, 2)));
+        assert_eq!(buckets.last(), Some(&(19, 2)));
+        assert!(buckets.iter().all(|&(_, count)| count == 2));
+    }
+
+    #[test]
+    fn test_fewer_totals_than_buckets() {
+        let input = format!("{}\n\n{}", u64::MAX - 2, u64::MAX);
+        let stats = Stats::new(&parse_elves(input.lines()).unwrap()).unwrap();
+
+        assert_eq!(
+            stats.buckets(10),
+            vec![(u64::MAX - 2, 1), (u64::MAX - 1, 0), (u64::MAX, 1)]
+        );
+        // 6 totals in buckets of 2
+        let stats = Stats::new(&parse_elves("0\n\n5".lines()).unwrap()).unwrap();
+        assert_eq!(stats.buckets(4), vec![(0, 1), (2, 0), (4, 1)]);
+    }
+
+    #[test]
+    fn test_histogram() {
+        assert_eq!(
+            stats().histogram(4, 6),
+            " 4000 | ###### 2\n 9000 | ###### 2\n14000 |        0\n19000 | ###    1\n"
+        );
+    }
+
+    #[test]
+    fn test_single_elf() {
+        let stats = Stats::new(&parse_elves("5\n5".lines()).unwrap()).unwrap();
+
+        assert_eq!(stats.median(), 10.0);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.buckets(2), vec![(10, 1)]);
+    }
+}
@@ -0,0 +1,53 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// The `n` largest values, largest first.
+///
+/// Only a heap of the current top `n` is kept while consuming the
+/// iterator, so the values never have to be collected or sorted.
+pub fn top_n<T: Ord>(values: impl IntoIterator<Item = T>, n: usize) -> Vec<T> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // no larger than the values, so any `n` works without allocating for it
+    let values = values.into_iter();
+    let mut heap = BinaryHeap::with_capacity(n.min(values.size_hint().0));
+
+    for value in values {
+        if heap.len() < n {
+            heap.push(Reverse(value));
+        } else if let Some(mut smallest) = heap.peek_mut() {
+            if value > smallest.0 {
+                *smallest = Reverse(value);
+            }
+        }
+    }
+
+    // ascending order of `Reverse` is descending order of the values
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(value)| value)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(vec![], 3, vec![]; "empty")]
+    #[test_case(vec![1, 2, 3], 0, vec![]; "zero")]
+    #[test_case(vec![5, 1, 4, 2, 3], 1, vec![5]; "max")]
+    #[test_case(vec![5, 1, 4, 2, 3], 3, vec![5, 4, 3]; "three")]
+    #[test_case(vec![2, 1], 3, vec![2, 1]; "fewer than n")]
+    #[test_case(vec![3, 1, 3, 2, 3], 2, vec![3, 3]; "ties")]
+    #[test_case(vec![1, 3, 2], usize::MAX, vec![3, 2, 1]; "any n")]
+    fn test_top_n(values: Vec<u64>, n: usize, expected: Vec<u64>) {
+        assert_eq!(top_n(values, n), expected);
+    }
+
+    #[test]
+    fn test_top_n_streaming() {
+        assert_eq!(top_n((0..1_000_000u64).rev(), 2), [999_999, 999_998]);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{parse_line, top_n, ParseError};
+use std::collections::BTreeMap;
+
+/// Keeps track of the elves while the inventory is fed to it line by line,
+/// so it can be queried at any point without having the whole input.
+///
+/// The elf currently being filled counts as an elf in all queries, just
+/// like the last elf of a finished input.
+#[derive(Debug, Default)]
+pub struct CalorieTracker {
+    /// totals of the finished elves -> number of elves with that total
+    finished: BTreeMap<u64, usize>,
+    current: Option<u64>,
+    total: u64,
+    /// number of pushed lines, items and elf boundaries
+    pushed: usize,
+}
+
+impl CalorieTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next input line, which is either an item or an empty line
+    /// separating the elves.
+    pub fn push_line(&mut self, line: &str) -> Result<(), ParseError> {
+        self.pushed += 1;
+
+        match parse_line(line, self.pushed)? {
+            Some(calories) => self.add(calories),
+            None => {
+                self.finish();
+                Ok(())
+            }
+        }
+    }
+
+    /// Add an item to the current elf.
+    pub fn push_item(&mut self, calories: u64) -> Result<(), ParseError> {
+        self.pushed += 1;
+        self.add(calories)
+    }
+
+    /// Finish the current elf, following items go to a new one.
+    pub fn end_elf(&mut self) {
+        self.pushed += 1;
+        self.finish();
+    }
+
+    fn add(&mut self, calories: u64) -> Result<(), ParseError> {
+        let overflow = ParseError::SumOverflow { line: self.pushed };
+
+        let sum = self
+            .current
+            .unwrap_or(0)
+            .checked_add(calories)
+            .ok_or_else(|| overflow.clone())?;
+        self.total = self.total.checked_add(calories).ok_or(overflow)?;
+        self.current = Some(sum);
+        Ok(())
+    }
+
+    fn finish(&mut self) {
+        if let Some(sum) = self.current.replace(0) {
+            *self.finished.entry(sum).or_default() += 1;
+        }
+    }
+
+    pub fn elves(&self) -> usize {
+        self.finished.values().sum::<usize>() + usize::from(self.current.is_some())
+    }
+
+    /// calories carried by all elves together.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// the most calories a single elf carries, `None` before any elf.
+    pub fn max(&self) -> Option<u64> {
+        self.top(1).first().copied()
+    }
+
+    /// the `n` largest totals, largest first.
+    pub fn top(&self, n: usize) -> Vec<u64> {
+        let finished = self
+            .finished
+            .iter()
+            .rev()
+            .flat_map(|(&sum, &count)| std::iter::repeat_n(sum, count))
+            .take(n);
+
+        top_n(finished.chain(self.current), n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn test_empty() {
+        let tracker = CalorieTracker::new();
+
+        assert_eq!(tracker.elves(), 0);
+        assert_eq!(tracker.max(), None);
+        assert_eq!(tracker.top(3), vec![]);
+        assert_eq!(tracker.total(), 0);
+    }
+
+    #[test]
+    fn test_same_as_parts() {
+        let mut tracker = CalorieTracker::new();
+        for line in TEST_INPUT.lines() {
+            tracker.push_line(line).unwrap();
+        }
+
+        assert_eq!(tracker.elves(), 5);
+        assert_eq!(tracker.max(), Some(24000));
+        assert_eq!(tracker.top(3).iter().sum::<u64>(), 45000);
+        assert_eq!(tracker.top(10), vec![24000, 11000, 10000, 6000, 4000]);
+        assert_eq!(tracker.total(), 55000);
+    }
+
+    #[test]
+    fn test_live_queries() {
+        let mut tracker = CalorieTracker::new();
+
+        tracker.push_item(3).unwrap();
+        assert_eq!(tracker.max(), Some(3));
+
+        tracker.end_elf();
+        tracker.push_item(1).unwrap();
+        assert_eq!(tracker.top(2), vec![3, 1]);
+
+        tracker.push_item(4).unwrap();
+        assert_eq!(tracker.top(2), vec![5, 3]);
+        assert_eq!(tracker.total(), 8);
+
+        tracker.end_elf();
+        tracker.end_elf();
+        assert_eq!(tracker.elves(), 4);
+        assert_eq!(tracker.top(5), vec![5, 3, 0, 0]);
+    }
+
+    #[test]
+    fn test_equal_totals() {
+        let mut tracker = CalorieTracker::new();
+        for line in "2\n\n2\n\n1\n1\n\n1".lines() {
+            tracker.push_line(line).unwrap();
+        }
+
+        assert_eq!(tracker.top(3), vec![2, 2, 2]);
+        assert_eq!(tracker.elves(), 4);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut tracker = CalorieTracker::new();
+        tracker.push_line("1").unwrap();
+
+        assert_eq!(
+            tracker.push_line("x"),
+            Err(ParseError::InvalidNumber {
+                line: 2,
+                content: "x".into()
+            })
+        );
+        assert_eq!(
+            tracker.push_item(u64::MAX),
+            Err(ParseError::SumOverflow { line: 3 })
+        );
+        // failed pushes don't change the elves
+        assert_eq!(tracker.top(2), vec![1]);
+        assert_eq!(tracker.total(), 1);
+    }
+}
@@ -0,0 +1,27 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use d02::{part_1, part_2};
+
+static INPUT: &str = include_str!("../input.txt");
+
+fn lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("d02");
+
+    group.bench_function("owned lines", |b| {
+        b.iter(|| {
+            let lines: Vec<String> = black_box(INPUT).lines().map(|l| l.to_string()).collect();
+            (part_1(lines.iter()), part_2(lines.iter()))
+        })
+    });
+
+    group.bench_function("borrowed lines", |b| {
+        b.iter(|| {
+            let input = black_box(INPUT);
+            (part_1(input.lines()), part_2(input.lines()))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, lines);
+criterion_main!(benches);
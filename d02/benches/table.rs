@@ -0,0 +1,29 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use d02::{Part, Rules, ScoreTable};
+
+static INPUT: &str = include_str!("../input.txt");
+
+fn table(c: &mut Criterion) {
+    // the puzzle input is small, repeat it to get to a few megabytes
+    let input = format!("{}\n", INPUT.trim_end()).repeat(200);
+    let rules = Rules::default();
+
+    let mut group = c.benchmark_group("d02 score");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    for (name, part) in [("part 1", Part::One), ("part 2", Part::Two)] {
+        group.bench_function(format!("rules {}", name), |b| {
+            b.iter(|| rules.total(part, black_box(&input).lines()))
+        });
+
+        let table = ScoreTable::new(&rules, part).unwrap();
+        group.bench_function(format!("table {}", name), |b| {
+            b.iter(|| table.score(black_box(input.as_bytes())))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, table);
+criterion_main!(benches);
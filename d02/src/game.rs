@@ -0,0 +1,261 @@
+use crate::Outcome;
+use std::fmt;
+
+/// A move of a [`Game`], the index into its list of moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Move(pub usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    /// only an odd number of at least 3 moves can be balanced
+    InvalidMoveCount(usize),
+    DuplicateMove(String),
+    UnknownMove(String),
+    BeatsItself(String),
+    /// both moves are said to beat the other one
+    BothBeat(String, String),
+    /// neither move beats the other one
+    Undecided(String, String),
+    /// the move doesn't beat exactly half of the other moves
+    Unbalanced(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::InvalidMoveCount(count) => {
+                write!(
+                    f,
+                    "a game needs an odd number of at least 3 moves, got {}",
+                    count
+                )
+            }
+            GameError::DuplicateMove(name) => write!(f, "move {:?} is defined twice", name),
+            GameError::UnknownMove(name) => write!(f, "unknown move {:?}", name),
+            GameError::BeatsItself(name) => write!(f, "move {:?} beats itself", name),
+            GameError::BothBeat(a, b) => write!(f, "{:?} and {:?} both beat each other", a, b),
+            GameError::Undecided(a, b) => write!(f, "neither {:?} nor {:?} wins", a, b),
+            GameError::Unbalanced(name) => {
+                write!(f, "move {:?} doesn't beat exactly half of the others", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// The moves of a game like Rock-Paper-Scissors and which move beats which.
+///
+/// Every move beats exactly half of the other moves and loses against the
+/// rest, so for every move of the other player there is a move to win, lose
+/// or draw with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    /// `beats[i][j]`: move `i` beats move `j`
+    beats: Vec<Vec<bool>>,
+}
+
+impl Game {
+    /// A game with the moves `names`, where `(a, b)` in `beats` means that
+    /// `a` beats `b`. Every pair of different moves must appear once.
+    pub fn new(names: &[&str], beats: &[(&str, &str)]) -> Result<Game, GameError> {
+        let n = names.len();
+        let mut game = Game::empty(names)?;
+
+        for (winner, loser) in beats {
+            let (w, l) = (game.find(winner)?, game.find(loser)?);
+            if w == l {
+                return Err(GameError::BeatsItself(winner.to_string()));
+            }
+            if game.beats[l.0][w.0] {
+                return Err(GameError::BothBeat(winner.to_string(), loser.to_string()));
+            }
+            game.beats[w.0][l.0] = true;
+        }
+
+        for i in 0..n {
+            for j in i + 1..n {
+                if !game.beats[i][j] && !game.beats[j][i] {
+                    return Err(GameError::Undecided(names[i].into(), names[j].into()));
+                }
+            }
+        }
+        for (i, row) in game.beats.iter().enumerate() {
+            if row.iter().filter(|&&b| b).count() != n / 2 {
+                return Err(GameError::Unbalanced(names[i].into()));
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// A game where move `i` beats move `j` if `i - j` is odd modulo the
+    /// number of moves, as in Rock-Paper-Scissors(-Spock-Lizard).
+    pub fn cyclic(names: &[&str]) -> Result<Game, GameError> {
+        let n = names.len();
+        let mut game = Game::empty(names)?;
+
+        for (i, row) in game.beats.iter_mut().enumerate() {
+            for (j, beats) in row.iter_mut().enumerate() {
+                *beats = ((n + i - j) % n) % 2 == 1;
+            }
+        }
+        Ok(game)
+    }
+
+    fn empty(names: &[&str]) -> Result<Game, GameError> {
+        let n = names.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(GameError::InvalidMoveCount(n));
+        }
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(GameError::DuplicateMove(name.to_string()));
+            }
+        }
+
+        Ok(Game {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            beats: vec![vec![false; n]; n],
+        })
+    }
+
+    /// Rock-Paper-Scissors
+    pub fn rps() -> Game {
+        Game::cyclic(&["Rock", "Paper", "Scissors"]).expect("valid game")
+    }
+
+    /// Rock-Paper-Scissors-Spock-Lizard
+    pub fn rpsls() -> Game {
+        Game::cyclic(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]).expect("valid game")
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.len()).map(Move)
+    }
+
+    pub fn name(&self, m: Move) -> &str {
+        &self.names[m.0]
+    }
+
+    /// the move called `name`
+    pub fn find(&self, name: &str) -> Result<Move, GameError> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(Move)
+            .ok_or_else(|| GameError::UnknownMove(name.to_string()))
+    }
+
+    pub fn beats(&self, a: Move, b: Move) -> bool {
+        self.beats[a.0][b.0]
+    }
+
+    pub fn play(&self, my_move: Move, other_move: Move) -> Outcome {
+        if self.beats(my_move, other_move) {
+            Outcome::Win
+        } else if self.beats(other_move, my_move) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// Points for playing a move, its position in the game starting at 1.
+    pub fn points(&self, m: Move) -> u64 {
+        m.0 as u64 + 1
+    }
+
+    /// The moves leading to `outcome` against `other_move`.
+    pub fn moves_for(&self, other_move: Move, outcome: Outcome) -> impl Iterator<Item = Move> + '_ {
+        self.moves()
+            .filter(move |&m| self.play(m, other_move) == outcome)
+    }
+
+    /// The move for `outcome` against `other_move` scoring the most points.
+    pub fn choose(&self, other_move: Move, outcome: Outcome) -> Move {
+        self.moves_for(other_move, outcome)
+            .max_by_key(|&m| self.points(m))
+            .expect("every outcome is possible in a balanced game")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn names(game: &Game, moves: impl Iterator<Item = Move>) -> Vec<&str> {
+        moves.map(|m| game.name(m)).collect()
+    }
+
+    #[test_case("Rock", "Scissors", Outcome::Win)]
+    #[test_case("Scissors", "Paper", Outcome::Win)]
+    #[test_case("Paper", "Rock", Outcome::Win)]
+    #[test_case("Rock", "Paper", Outcome::Lose)]
+    #[test_case("Paper", "Paper", Outcome::Draw)]
+    fn test_rps(my_move: &str, other_move: &str, expected: Outcome) {
+        let game = Game::rps();
+        let (my_move, other_move) = (game.find(my_move).unwrap(), game.find(other_move).unwrap());
+
+        assert_eq!(game.play(my_move, other_move), expected);
+    }
+
+    #[test]
+    fn test_rpsls_is_the_explicit_game() {
+        let explicit = Game::new(
+            &["Rock", "Paper", "Scissors", "Spock", "Lizard"],
+            &[
+                ("Scissors", "Paper"),
+                ("Paper", "Rock"),
+                ("Rock", "Lizard"),
+                ("Lizard", "Spock"),
+                ("Spock", "Scissors"),
+                ("Scissors", "Lizard"),
+                ("Lizard", "Paper"),
+                ("Paper", "Spock"),
+                ("Spock", "Rock"),
+                ("Rock", "Scissors"),
+            ],
+        );
+
+        assert_eq!(explicit, Ok(Game::rpsls()));
+    }
+
+    #[test]
+    fn test_moves_for() {
+        let game = Game::rpsls();
+        let spock = game.find("Spock").unwrap();
+
+        assert_eq!(
+            names(&game, game.moves_for(spock, Outcome::Win)),
+            vec!["Paper", "Lizard"]
+        );
+        assert_eq!(
+            names(&game, game.moves_for(spock, Outcome::Lose)),
+            vec!["Rock", "Scissors"]
+        );
+        assert_eq!(game.name(game.choose(spock, Outcome::Win)), "Lizard");
+        assert_eq!(game.choose(spock, Outcome::Draw), spock);
+    }
+
+    #[test_case(&["A", "B"], &[("A", "B")], GameError::InvalidMoveCount(2))]
+    #[test_case(&["A", "B", "A"], &[], GameError::DuplicateMove("A".into()))]
+    #[test_case(&["A", "B", "C"], &[("A", "D")], GameError::UnknownMove("D".into()))]
+    #[test_case(&["A", "B", "C"], &[("A", "A")], GameError::BeatsItself("A".into()))]
+    #[test_case(&["A", "B", "C"], &[("A", "B"), ("B", "A")], GameError::BothBeat("B".into(), "A".into()))]
+    #[test_case(&["A", "B", "C"], &[("A", "B"), ("B", "C")], GameError::Undecided("A".into(), "C".into()))]
+    #[test_case(&["A", "B", "C"], &[("A", "B"), ("A", "C"), ("B", "C")], GameError::Unbalanced("A".into()))]
+    fn test_invalid_games(names: &[&str], beats: &[(&str, &str)], expected: GameError) {
+        assert_eq!(Game::new(names, beats), Err(expected));
+    }
+}
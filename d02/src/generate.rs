@@ -0,0 +1,445 @@
+use crate::{Part, Rules};
+use std::fmt;
+
+/// No guide with that many rounds reaches the target score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Impossible {
+    pub rounds: usize,
+    pub target: u64,
+    /// the reachable totals closest to the target
+    pub below: Option<u64>,
+    pub above: Option<u64>,
+}
+
+impl fmt::Display for Impossible {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no guide of {} rounds scores {}",
+            self.rounds, self.target
+        )?;
+        match (self.below, self.above) {
+            (Some(below), Some(above)) => write!(f, ", closest are {} and {}", below, above),
+            (Some(closest), None) | (None, Some(closest)) => write!(f, ", closest is {}", closest),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for Impossible {}
+
+/// A guide that can't be generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    Impossible(Impossible),
+    /// the rules have no letters for a round
+    NoLetters,
+    /// the guide wouldn't fit in memory
+    TooManyRounds(usize),
+    /// the scores of a round are too far apart to tell which totals exist
+    ScoresTooFarApart,
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::Impossible(err) => err.fmt(f),
+            GenerateError::NoLetters => write!(f, "the rules have no letters for a round"),
+            GenerateError::TooManyRounds(rounds) => {
+                write!(f, "a guide of {} rounds is too large", rounds)
+            }
+            GenerateError::ScoresTooFarApart => {
+                write!(f, "the scores of a round are too far apart")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+impl From<Impossible> for GenerateError {
+    fn from(err: Impossible) -> Self {
+        GenerateError::Impossible(err)
+    }
+}
+
+/// The most steps [`Totals`] counts the fewest steps for.
+const MAX_COUNTED: u64 = 1 << 22;
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The totals of guides by their number of rounds.
+///
+/// Every score is the lowest one plus some steps of the gcd of their
+/// differences. As a round can always take the lowest score, `k` rounds
+/// total `k * lowest + gcd * t` exactly when `t` is a sum of at most `k`
+/// nonzero steps. Any `m` steps have some that add up to a multiple of `m`,
+/// which no more steps of the largest size `m` replace, so the fewest steps
+/// adding up to `t` use fewer than `m` smaller ones. Above `(m - 1)²` they
+/// always use a step of `m`, and only the fewest steps up to there need to
+/// be counted.
+struct Totals {
+    lowest: u64,
+    gcd: u64,
+    largest: u64,
+    /// the fewest steps adding up to every `t` up to `(m - 1)²`
+    fewest: Vec<u32>,
+}
+
+impl Totals {
+    /// `None` if the largest step is too large to count the steps up to
+    /// its square.
+    fn new(scores: &[u64]) -> Option<Totals> {
+        let lowest = scores.iter().copied().min().unwrap_or(0);
+        let gcd = scores
+            .iter()
+            .fold(0, |gcd, &score| self::gcd(gcd, score - lowest));
+        let steps: Vec<u64> = scores
+            .iter()
+            .filter(|&&score| score > lowest)
+            .map(|&score| (score - lowest) / gcd)
+            .collect();
+        let largest = steps.iter().copied().max().unwrap_or(0);
+
+        let counted = largest
+            .saturating_sub(1)
+            .checked_mul(largest.saturating_sub(1))?;
+        if counted > MAX_COUNTED {
+            return None;
+        }
+        let mut fewest = vec![u32::MAX; counted as usize + 1];
+        fewest[0] = 0;
+        for t in 1..fewest.len() {
+            fewest[t] = steps
+                .iter()
+                .filter(|&&step| step as usize <= t)
+                .map(|&step| fewest[t - step as usize].saturating_add(1))
+                .min()
+                .unwrap_or(u32::MAX);
+        }
+
+        Some(Totals {
+            lowest,
+            gcd: gcd.max(1),
+            largest,
+            fewest,
+        })
+    }
+
+    /// The fewest steps adding up to `t`, `u64::MAX` if none do.
+    fn fewest(&self, t: u64) -> u64 {
+        let counted = self.fewest.len() as u64 - 1;
+        if t <= counted {
+            return match self.fewest[t as usize] {
+                u32::MAX => u64::MAX,
+                steps => steps as u64,
+            };
+        }
+        if self.largest == 0 {
+            return u64::MAX;
+        }
+        let count = (t - counted).div_ceil(self.largest);
+        self.fewest(t - count * self.largest).saturating_add(count)
+    }
+
+    /// The lowest total of `rounds` rounds, `None` if it doesn't fit.
+    fn base(&self, rounds: u64) -> Option<u64> {
+        rounds.checked_mul(self.lowest)
+    }
+
+    fn contains(&self, rounds: u64, total: u64) -> bool {
+        match self.base(rounds) {
+            Some(base) if total >= base && (total - base).is_multiple_of(self.gcd) => {
+                self.fewest((total - base) / self.gcd) <= rounds
+            }
+            _ => false,
+        }
+    }
+
+    /// The totals of `rounds` rounds closest below and above `target`.
+    ///
+    /// Totals only leave gaps near the lowest and the highest one, so this
+    /// only looks at a few steps on either side.
+    fn closest(&self, rounds: u64, target: u64) -> (Option<u64>, Option<u64>) {
+        let Some(base) = self.base(rounds) else {
+            return (None, None);
+        };
+        // the steps of the highest total, if it fits
+        let top = rounds.checked_mul(self.largest);
+        let total = |t: u64| t.checked_mul(self.gcd)?.checked_add(base);
+
+        let below = target.checked_sub(base).map(|rest| {
+            let mut t = (rest / self.gcd).min(top.unwrap_or(u64::MAX));
+            while self.fewest(t) > rounds {
+                t -= 1;
+            }
+            t
+        });
+
+        let mut t = target
+            .checked_sub(base)
+            .map_or(0, |rest| rest / self.gcd + 1);
+        let above = loop {
+            if top.is_some_and(|top| t > top) {
+                break None;
+            }
+            if self.fewest(t) <= rounds {
+                break Some(t);
+            }
+            t += 1;
+        };
+
+        (below.and_then(total), above.and_then(total))
+    }
+}
+
+/// Build a strategy guide of `rounds` rounds scoring exactly `target`
+/// under `rules`, reading the second column as `part` does.
+///
+/// Every round scores one of the few points a single round can give, and
+/// [`Totals`] tells without going through the rounds whether some number
+/// of them reaches a total. If `target` isn't a total of all rounds, no
+/// guide exists. Otherwise every round takes the highest score that still
+/// leaves a total of the remaining rounds, which needs no memory besides
+/// the guide.
+pub fn generate(
+    rules: &Rules,
+    part: Part,
+    rounds: usize,
+    target: u64,
+) -> Result<String, GenerateError> {
+    // one round for every score a round can have
+    let mut choices: Vec<(u64, char, char)> = Vec::new();
+    for other in rules.opponent_letters() {
+        let other_move = rules.opponent_move(other).expect("known letter");
+        for second in rules.second_letters(part) {
+            let my_move = rules
+                .second_move(part, other_move, second)
+                .expect("known letter");
+            let score = rules.score(my_move, other_move);
+            if !choices.iter().any(|&(s, _, _)| s == score) {
+                choices.push((score, other, second));
+            }
+        }
+    }
+    choices.sort_by_key(|&(score, _, _)| std::cmp::Reverse(score));
+    if choices.is_empty() {
+        return Err(GenerateError::NoLetters);
+    }
+
+    let scores: Vec<u64> = choices.iter().map(|&(score, _, _)| score).collect();
+    let totals = Totals::new(&scores).ok_or(GenerateError::ScoresTooFarApart)?;
+
+    if !totals.contains(rounds as u64, target) {
+        let (below, above) = totals.closest(rounds as u64, target);
+        return Err(Impossible {
+            rounds,
+            target,
+            below,
+            above,
+        }
+        .into());
+    }
+
+    let too_many = GenerateError::TooManyRounds(rounds);
+    let capacity = rounds.checked_mul(4).ok_or(too_many.clone())?;
+    let mut guide = String::new();
+    guide.try_reserve_exact(capacity).map_err(|_| too_many)?;
+
+    let mut total = target;
+    for left in (0..rounds as u64).rev() {
+        let &(score, other, second) = choices
+            .iter()
+            .find(|&&(score, _, _)| score <= total && totals.contains(left, total - score))
+            .expect("a total is reached from a total of one round less");
+
+        total -= score;
+        guide.push(other);
+        guide.push(' ');
+        guide.push(second);
+        guide.push('\n');
+    }
+
+    Ok(guide)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Part::One, 3, 15)]
+    #[test_case(Part::Two, 3, 12)]
+    #[test_case(Part::One, 0, 0; "no rounds")]
+    #[test_case(Part::One, 1, 9; "best round")]
+    #[test_case(Part::Two, 10, 10; "worst rounds")]
+    #[test_case(Part::Two, 100, 517)]
+    #[test_case(Part::One, 2500, 15422; "puzzle sized")]
+    fn test_generate(part: Part, rounds: usize, target: u64) {
+        let rules = Rules::default();
+        let guide = generate(&rules, part, rounds, target).unwrap();
+
+        assert_eq!(guide.lines().count(), rounds);
+        assert_eq!(rules.total(part, guide.lines()), Ok(target));
+    }
+
+    #[test]
+    fn test_impossible() {
+        let rules = Rules::default();
+
+        assert_eq!(
+            generate(&rules, Part::One, 1, 10),
+            Err(GenerateError::Impossible(Impossible {
+                rounds: 1,
+                target: 10,
+                below: Some(9),
+                above: None
+            }))
+        );
+        assert_eq!(
+            generate(&rules, Part::Two, 2, 1).unwrap_err().to_string(),
+            "no guide of 2 rounds scores 1, closest is 2"
+        );
+        assert_eq!(
+            generate(&rules, Part::One, 0, 3).unwrap_err().to_string(),
+            "no guide of 0 rounds scores 3, closest is 0"
+        );
+        assert_eq!(
+            generate(&rules, Part::One, 3, 2).unwrap_err().to_string(),
+            "no guide of 3 rounds scores 2, closest is 3"
+        );
+    }
+
+    #[test]
+    fn test_gaps() {
+        // only draws and wins, worth 2 and 5 with no points for the moves
+        let rules = Rules::from_toml(
+            r#"
+            outcome_points = { win = 5, draw = 2, lose = 2 }
+            [points]
+            Rock = 0
+            Paper = 0
+            Scissors = 0
+            "#,
+        )
+        .unwrap();
+
+        // three rounds of 2 or 5 points only add up to 6, 9, 12 or 15
+        assert!(generate(&rules, Part::One, 3, 12).is_ok());
+        assert_eq!(
+            generate(&rules, Part::One, 3, 10).unwrap_err(),
+            GenerateError::Impossible(Impossible {
+                rounds: 3,
+                target: 10,
+                below: Some(9),
+                above: Some(12)
+            })
+        );
+    }
+
+    #[test_case(&[1, 2, 3, 4, 5, 6, 7, 8, 9]; "every score")]
+    #[test_case(&[2, 5]; "gaps")]
+    #[test_case(&[3, 10, 11, 17]; "gaps at both ends")]
+    #[test_case(&[4, 10, 16]; "common divisor")]
+    #[test_case(&[7]; "one score")]
+    fn test_totals(scores: &[u64]) {
+        let totals = Totals::new(scores).unwrap();
+
+        // every total of guides of up to 30 rounds, one round at a time
+        let mut reachable = vec![0];
+        for rounds in 0..30u64 {
+            let highest = reachable.iter().max().copied().unwrap();
+            for total in 0..highest + 20 {
+                assert_eq!(
+                    totals.contains(rounds, total),
+                    reachable.contains(&total),
+                    "{} rounds, total {}",
+                    rounds,
+                    total
+                );
+            }
+            reachable = reachable
+                .iter()
+                .flat_map(|total| scores.iter().map(move |score| total + score))
+                .collect();
+            reachable.sort_unstable();
+            reachable.dedup();
+        }
+    }
+
+    #[test]
+    fn test_no_letters() {
+        let rules = Rules::from_toml("[opponent]").unwrap();
+
+        assert_eq!(
+            generate(&rules, Part::One, 3, 0),
+            Err(GenerateError::NoLetters)
+        );
+        assert_eq!(
+            generate(&rules, Part::Two, 0, 0),
+            Err(GenerateError::NoLetters)
+        );
+    }
+
+    #[test]
+    fn test_far_apart() {
+        let rules = |draw| {
+            Rules::from_toml(&format!(
+                r#"
+                outcome_points = {{ win = 1000000000, draw = {}, lose = 0 }}
+                [points]
+                Rock = 0
+                Paper = 0
+                Scissors = 0
+                "#,
+                draw
+            ))
+            .unwrap()
+        };
+
+        // steps of a billion don't need a billion totals
+        assert_eq!(
+            generate(&rules(0), Part::One, 3, 5)
+                .unwrap_err()
+                .to_string(),
+            "no guide of 3 rounds scores 5, closest are 0 and 1000000000"
+        );
+        let guide = generate(&rules(0), Part::One, 3, 2_000_000_000).unwrap();
+        assert_eq!(rules(0).total(Part::One, guide.lines()), Ok(2_000_000_000));
+
+        assert_eq!(
+            generate(&rules(1), Part::One, 3, 5),
+            Err(GenerateError::ScoresTooFarApart)
+        );
+    }
+
+    #[test]
+    fn test_many_rounds() {
+        let rules = Rules::default();
+
+        // ends of the range, where every round takes the same score
+        let guide = generate(&rules, Part::Two, 1_000_000, 9_000_000).unwrap();
+        assert!(guide
+            .lines()
+            .all(|line| line == guide.lines().next().unwrap()));
+        let guide = generate(&rules, Part::Two, 1_000_000, 1_000_001).unwrap();
+        assert_eq!(rules.total(Part::Two, guide.lines()), Ok(1_000_001));
+
+        assert_eq!(
+            generate(&rules, Part::Two, 1_000_000, 9_000_001)
+                .unwrap_err()
+                .to_string(),
+            "no guide of 1000000 rounds scores 9000001, closest is 9000000"
+        );
+        assert_eq!(
+            generate(&rules, Part::One, usize::MAX, u64::MAX),
+            Err(GenerateError::TooManyRounds(usize::MAX))
+        );
+    }
+}
@@ -0,0 +1,158 @@
+mod game;
+mod generate;
+mod mappings;
+mod model;
+mod parse;
+mod report;
+mod rules;
+mod table;
+mod tournament;
+
+pub use game::{Game, GameError, Move};
+pub use generate::{generate, GenerateError, Impossible};
+pub use mappings::{evaluate_all, find_score, Evaluation, Interpretation, LETTERS};
+pub use model::{Analysis, OpponentModel};
+pub use parse::{ParseError, UnknownLetter};
+pub use report::{Report, RoundReport};
+pub use rules::{OutcomePoints, Part, Round, Rules, RulesError};
+use serde::Deserialize;
+use std::fmt;
+pub use table::{fast_total, ScoreTable};
+pub use tournament::{
+    default_strategies, round_robin, FixedGuide, FrequencyCounter, GuideError, PatternPredictor,
+    Random, Standing, Standings, Strategy,
+};
+
+#[derive(PartialEq, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+enum RPC {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+/// The moves of the strategy guide are the moves of [`Game::rps`].
+impl From<RPC> for Move {
+    fn from(m: RPC) -> Self {
+        match m {
+            RPC::Rock => Move(0),
+            RPC::Paper => Move(1),
+            RPC::Scissors => Move(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+impl Outcome {
+    pub fn points(&self) -> u64 {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+        }
+    }
+}
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Win => "win",
+            Outcome::Lose => "lose",
+            Outcome::Draw => "draw",
+        })
+    }
+}
+impl TryFrom<char> for Outcome {
+    type Error = UnknownLetter;
+
+    fn try_from(s: char) -> Result<Self, Self::Error> {
+        match s {
+            'X' => Ok(Outcome::Lose),
+            'Y' => Ok(Outcome::Draw),
+            'Z' => Ok(Outcome::Win),
+            _ => Err(UnknownLetter(s)),
+        }
+    }
+}
+
+impl TryFrom<char> for RPC {
+    type Error = UnknownLetter;
+
+    fn try_from(s: char) -> Result<Self, Self::Error> {
+        match s {
+            'A' => Ok(RPC::Rock),
+            'B' => Ok(RPC::Paper),
+            'C' => Ok(RPC::Scissors),
+            'X' => Ok(RPC::Rock),
+            'Y' => Ok(RPC::Paper),
+            'Z' => Ok(RPC::Scissors),
+            _ => Err(UnknownLetter(s)),
+        }
+    }
+}
+
+pub fn part_1<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> Result<u64, ParseError> {
+    Rules::default().part_1(lines)
+}
+
+pub fn part_2<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> Result<u64, ParseError> {
+    Rules::default().part_2(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = "
+        A Y
+        B X
+        C Z";
+
+    #[test]
+    fn test_1() {
+        assert_eq!(part_1(TEST_INPUT.lines()), Ok(15))
+    }
+
+    #[test]
+    fn test_2() {
+        assert_eq!(part_2(TEST_INPUT.lines()), Ok(12))
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(Outcome::try_from('Z'), Ok(Outcome::Win));
+        assert_eq!(RPC::try_from('B'), Ok(RPC::Paper));
+        assert_eq!(RPC::try_from('D'), Err(UnknownLetter('D')));
+        assert_eq!(Outcome::try_from('A'), Err(UnknownLetter('A')));
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "A Y\nB X\nC Q";
+        let expected = Err(ParseError::UnknownLetter {
+            line: 3,
+            column: 3,
+            letter: 'Q',
+        });
+
+        assert_eq!(part_1(input.lines()), expected);
+        assert_eq!(part_2(input.lines()), expected);
+        assert_eq!(
+            part_1("  D X".lines()),
+            Err(ParseError::UnknownLetter {
+                line: 1,
+                column: 3,
+                letter: 'D'
+            })
+        );
+        assert!(matches!(
+            part_1("A Y\nB XX".lines()),
+            Err(ParseError::TrailingGarbage { line: 2, .. })
+        ));
+    }
+}
@@ -1,149 +1,162 @@
-fn main() {
-    let lines: Vec<String> = std::fs::read_to_string(std::env::args().nth(1).unwrap())
-        .unwrap()
-        .lines()
-        .map(|l| l.to_string())
-        .collect();
-
-    println!("day 1: {}", part_1(lines.iter()));
-    println!("day 2: {}", part_2(lines.iter()));
+use d02::{
+    default_strategies, evaluate_all, fast_total, generate, round_robin, FixedGuide, OpponentModel,
+    Part, Report, Rules,
+};
+use std::{fmt::Display, str::FromStr};
+
+const USAGE: &str = "usage: d02 <input> [--rules <rules.toml|rules.json>] \
+                     [--mappings [score] | --report | --csv <1|2> \
+                     | --tournament <rounds> [seed] | --model \
+                     | --generate <1|2> <rounds> <score>]";
+
+enum Mode {
+    Solve,
+    /// all mappings, or only the ones reaching the score
+    Mappings(Option<u64>),
+    /// round by round tables for both parts
+    Report,
+    Csv(Part),
+    /// the guide against the built-in strategies
+    Tournament {
+        rounds: usize,
+        seed: u64,
+    },
+    /// the opponent's moves and the best responses to them
+    Model,
+    /// a guide of that many rounds reaching the score, ignoring the input
+    Generate {
+        part: Part,
+        rounds: usize,
+        score: u64,
+    },
 }
 
-#[derive(PartialEq, Debug)]
-enum RPC {
-    Rock,
-    Paper,
-    Scissors,
+fn usage(problem: &str) -> ! {
+    eprintln!("{}\n{}", problem, USAGE);
+    std::process::exit(2);
 }
 
-impl RPC {
-    fn points(&self) -> u64 {
-        match self {
-            RPC::Rock => 1,
-            RPC::Paper => 2,
-            RPC::Scissors => 3,
-        }
-    }
-    fn iter() -> impl Iterator<Item = &'static RPC> {
-        static OPTIONS: [RPC; 3] = [RPC::Rock, RPC::Paper, RPC::Scissors];
-        OPTIONS.iter()
+fn parse_part(arg: Option<String>) -> Part {
+    match arg.as_deref() {
+        Some("1") => Part::One,
+        Some("2") => Part::Two,
+        _ => usage(&format!("invalid part {:?}", arg.unwrap_or_default())),
     }
 }
 
-#[derive(Debug, PartialEq)]
-enum Outcome {
-    Win,
-    Lose,
-    Draw,
-}
-impl Outcome {
-    fn points(&self) -> u64 {
-        match self {
-            Outcome::Win => 6,
-            Outcome::Lose => 0,
-            Outcome::Draw => 3,
-        }
-    }
-}
-impl From<char> for Outcome {
-    fn from(s: char) -> Self {
-        match s {
-            'X' => Outcome::Lose,
-            'Y' => Outcome::Draw,
-            'Z' => Outcome::Win,
-            _ => unreachable!("unknown input char"),
-        }
-    }
+/// The argument as a number, `what` names it in the usage error.
+fn parse_number<T: FromStr>(arg: Option<String>, what: &str) -> T {
+    let arg = arg.unwrap_or_else(|| usage(&format!("missing {}", what)));
+    arg.parse()
+        .unwrap_or_else(|_| usage(&format!("invalid {} {:?}", what, arg)))
 }
 
-fn play(my_move: &RPC, other_move: &RPC) -> Outcome {
-    if my_move == other_move {
-        Outcome::Draw
-    } else {
-        match (my_move, other_move) {
-            (RPC::Rock, RPC::Scissors) => Outcome::Win,
-            (RPC::Scissors, RPC::Rock) => Outcome::Lose,
-            (RPC::Scissors, RPC::Paper) => Outcome::Win,
-            (RPC::Paper, RPC::Scissors) => Outcome::Lose,
-            (RPC::Paper, RPC::Rock) => Outcome::Win,
-            (RPC::Rock, RPC::Paper) => Outcome::Lose,
-            _ => unreachable!("unknown winner, got {:?} {:?}", my_move, other_move),
+fn main() {
+    let mut rules = Rules::default();
+    let mut mode = Mode::Solve;
+
+    let mut args = std::env::args().skip(2).peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rules" => {
+                let path = args.next().unwrap_or_else(|| usage("missing rules file"));
+                rules = Rules::load(path).unwrap_or_else(|err| fail(err));
+            }
+            "--mappings" => {
+                let score = args.next_if(|arg| !arg.starts_with("--"));
+                mode = Mode::Mappings(score.map(|score| parse_number(Some(score), "score")));
+            }
+            "--report" => mode = Mode::Report,
+            "--model" => mode = Mode::Model,
+            "--csv" => mode = Mode::Csv(parse_part(args.next())),
+            "--tournament" => {
+                let rounds = parse_number(args.next(), "number of rounds");
+                let seed = args.next_if(|arg| !arg.starts_with("--"));
+                mode = Mode::Tournament {
+                    rounds,
+                    seed: seed.map_or(1, |seed| parse_number(Some(seed), "seed")),
+                };
+            }
+            "--generate" => {
+                let part = parse_part(args.next());
+                let rounds = parse_number(args.next(), "number of rounds");
+                let score = parse_number(args.next(), "score");
+                mode = Mode::Generate {
+                    part,
+                    rounds,
+                    score,
+                };
+            }
+            _ => usage(&format!("unknown argument {:?}", arg)),
         }
     }
-}
 
-impl From<char> for RPC {
-    fn from(s: char) -> Self {
-        match s {
-            'A' => RPC::Rock,
-            'B' => RPC::Paper,
-            'C' => RPC::Scissors,
-            'X' => RPC::Rock,
-            'Y' => RPC::Paper,
-            'Z' => RPC::Scissors,
-            _ => unreachable!("unknown input char"),
-        }
+    if let Mode::Generate {
+        part,
+        rounds,
+        score,
+    } = mode
+    {
+        let guide = generate(&rules, part, rounds, score).unwrap_or_else(|err| fail(err));
+        print!("{}", guide);
+        return;
     }
-}
 
-fn split_lines<T: AsRef<str>>(
-    lines: impl Iterator<Item = T>,
-) -> impl Iterator<Item = (char, char)> {
-    lines.filter_map(|line| {
-        let line = line.as_ref().trim();
-        if line.is_empty() {
-            return None;
+    let input = shared::input::load();
+    match mode {
+        Mode::Solve => {
+            let total = |part| fast_total(&rules, part, &input);
+            println!(
+                "day 1: {}",
+                total(Part::One).unwrap_or_else(|err| fail(err))
+            );
+            println!(
+                "day 2: {}",
+                total(Part::Two).unwrap_or_else(|err| fail(err))
+            );
         }
-        let (lhs, rhs) = line.split_once(' ').expect("could not split");
-
-        Some((lhs.chars().next().unwrap(), rhs.chars().next().unwrap()))
-    })
-}
-
-fn part_1<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> u64 {
-    split_lines(lines)
-        .map(|(other_char, my_char)| {
-            let other_move = RPC::from(other_char);
-            let my_move = RPC::from(my_char);
-
-            play(&my_move, &other_move).points() + my_move.points()
-        })
-        .sum::<u64>()
-}
-
-fn part_2<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> u64 {
-    split_lines(lines)
-        .map(|(other_char, wanted_outcome)| {
-            let other_move = RPC::from(other_char);
-
-            let wanted_outcome = Outcome::from(wanted_outcome);
-
-            for my_potential_move in RPC::iter() {
-                if play(my_potential_move, &other_move) == wanted_outcome {
-                    return wanted_outcome.points() + my_potential_move.points();
+        Mode::Mappings(target) => {
+            let evaluations = evaluate_all(&rules, input.lines()).unwrap_or_else(|err| fail(err));
+            for evaluation in evaluations {
+                if target.is_none_or(|target| evaluation.score == target) {
+                    println!(
+                        "{:>8}  {}",
+                        evaluation.score,
+                        evaluation.interpretation.describe(&rules)
+                    );
                 }
             }
-            unreachable!("no wanted move found");
-        })
-        .sum::<u64>()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    static TEST_INPUT: &str = "
-        A Y
-        B X
-        C Z";
-
-    #[test]
-    fn test_1() {
-        assert_eq!(part_1(TEST_INPUT.lines()), 15)
+        }
+        Mode::Report => {
+            for part in [Part::One, Part::Two] {
+                let report =
+                    Report::new(&rules, part, input.lines()).unwrap_or_else(|err| fail(err));
+                println!("{}", report);
+            }
+        }
+        Mode::Csv(part) => {
+            let report = Report::new(&rules, part, input.lines()).unwrap_or_else(|err| fail(err));
+            report
+                .write_csv(std::io::stdout().lock())
+                .unwrap_or_else(|err| fail(err));
+        }
+        Mode::Tournament { rounds, seed } => {
+            let guide =
+                FixedGuide::from_guide(&rules, input.lines()).unwrap_or_else(|err| fail(err));
+            let mut strategies = default_strategies(guide, seed);
+            print!("{}", round_robin(&rules, &mut strategies, rounds));
+        }
+        Mode::Model => {
+            let analysis = OpponentModel::from_guide(&rules, input.lines())
+                .and_then(|model| model.analyze(&rules, input.lines()))
+                .unwrap_or_else(|err| fail(err));
+            print!("{}", analysis);
+        }
+        Mode::Generate { .. } => unreachable!("handled before loading the input"),
     }
+}
 
-    #[test]
-    fn test_2() {
-        assert_eq!(part_2(TEST_INPUT.lines()), 12)
-    }
+fn fail(err: impl Display) -> ! {
+    eprintln!("{}", err);
+    std::process::exit(1);
 }
@@ -0,0 +1,194 @@
+use crate::{parse::split_lines, Move, Outcome, ParseError, Rules};
+use std::collections::BTreeMap;
+
+/// The letters of the second column, whose meaning is unknown.
+pub const LETTERS: [char; 3] = ['X', 'Y', 'Z'];
+
+/// A meaning of [`LETTERS`], in the same order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Interpretation {
+    /// the letters are my moves, as in part 1
+    Moves(Vec<Move>),
+    /// the letters are the wanted outcomes, as in part 2
+    Outcomes(Vec<Outcome>),
+}
+
+impl Interpretation {
+    /// e.g. `X=Rock Y=Paper Z=Scissors`
+    pub fn describe(&self, rules: &Rules) -> String {
+        let meanings: Vec<String> = match self {
+            Interpretation::Moves(moves) => moves
+                .iter()
+                .map(|&m| rules.game.name(m).to_string())
+                .collect(),
+            Interpretation::Outcomes(outcomes) => {
+                outcomes.iter().map(|outcome| outcome.to_string()).collect()
+            }
+        };
+
+        LETTERS
+            .iter()
+            .zip(meanings)
+            .map(|(letter, meaning)| format!("{}={}", letter, meaning))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    pub interpretation: Interpretation,
+    pub score: u64,
+}
+
+/// All ordered selections of `k` different items.
+fn permutations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+
+    let mut result = Vec::new();
+    for (i, &first) in items.iter().enumerate() {
+        let rest: Vec<T> = items[..i].iter().chain(&items[i + 1..]).copied().collect();
+        for mut tail in permutations(&rest, k - 1) {
+            tail.insert(0, first);
+            result.push(tail);
+        }
+    }
+    result
+}
+
+/// Score the guide for every way to map [`LETTERS`] to different moves of
+/// the game and to the three outcomes, highest score first.
+///
+/// The opponent's column is read with `rules`, as are the points.
+pub fn evaluate_all<T: AsRef<str>>(
+    rules: &Rules,
+    lines: impl Iterator<Item = T>,
+) -> Result<Vec<Evaluation>, ParseError> {
+    // how often each opponent move meets each letter, so every
+    // interpretation only needs to look at the distinct rounds
+    let mut rounds: BTreeMap<(Move, usize), u64> = BTreeMap::new();
+    for round in split_lines(lines) {
+        let (other_letter, letter) = round?;
+        let other_move = other_letter.resolve(rules.opponent_move(other_letter.letter))?;
+        let letter = letter.resolve(LETTERS.iter().position(|&l| l == letter.letter))?;
+        *rounds.entry((other_move, letter)).or_default() += 1;
+    }
+
+    let moves: Vec<Move> = rules.game.moves().collect();
+    let as_moves = permutations(&moves, LETTERS.len())
+        .into_iter()
+        .map(|moves| {
+            let score = rounds
+                .iter()
+                .map(|(&(other_move, letter), count)| {
+                    count * rules.score(moves[letter], other_move)
+                })
+                .sum();
+            Evaluation {
+                interpretation: Interpretation::Moves(moves),
+                score,
+            }
+        });
+
+    let outcomes = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+    let as_outcomes = permutations(&outcomes, LETTERS.len())
+        .into_iter()
+        .map(|outcomes| {
+            let score = rounds
+                .iter()
+                .map(|(&(other_move, letter), count)| {
+                    let my_move = rules.choose(other_move, outcomes[letter]);
+                    count * rules.score(my_move, other_move)
+                })
+                .sum();
+            Evaluation {
+                interpretation: Interpretation::Outcomes(outcomes),
+                score,
+            }
+        });
+
+    let mut evaluations: Vec<Evaluation> = as_moves.chain(as_outcomes).collect();
+    evaluations.sort_by_key(|e| std::cmp::Reverse(e.score));
+    Ok(evaluations)
+}
+
+/// The interpretations under which the guide scores exactly `score`.
+pub fn find_score<T: AsRef<str>>(
+    rules: &Rules,
+    lines: impl Iterator<Item = T>,
+    score: u64,
+) -> Result<Vec<Interpretation>, ParseError> {
+    Ok(evaluate_all(rules, lines)?
+        .into_iter()
+        .filter(|e| e.score == score)
+        .map(|e| e.interpretation)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2};
+
+    static TEST_INPUT: &str = "
+        A Y
+        B X
+        C Z";
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(permutations(&[1, 2, 3], 3).len(), 6);
+        assert_eq!(permutations(&[1, 2, 3, 4, 5], 3).len(), 60);
+        assert_eq!(permutations(&[1, 2], 2), vec![vec![1, 2], vec![2, 1]]);
+    }
+
+    #[test]
+    fn test_evaluate_all() {
+        let rules = Rules::default();
+        let evaluations = evaluate_all(&rules, TEST_INPUT.lines()).unwrap();
+
+        assert_eq!(evaluations.len(), 12);
+        let score = |description: &str| {
+            evaluations
+                .iter()
+                .find(|e| e.interpretation.describe(&rules) == description)
+                .unwrap()
+                .score
+        };
+        assert_eq!(
+            score("X=Rock Y=Paper Z=Scissors"),
+            part_1(TEST_INPUT.lines()).unwrap()
+        );
+        assert_eq!(
+            score("X=lose Y=draw Z=win"),
+            part_2(TEST_INPUT.lines()).unwrap()
+        );
+        // Rock against Rock, Scissors against Paper, Paper against Scissors
+        assert_eq!(score("X=Scissors Y=Rock Z=Paper"), 4 + 9 + 2);
+        assert!(evaluations.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_find_score() {
+        let rules = Rules::default();
+
+        let found: Vec<String> = find_score(&rules, TEST_INPUT.lines(), 15)
+            .unwrap()
+            .iter()
+            .map(|i| i.describe(&rules))
+            .collect();
+        assert!(found.contains(&"X=Rock Y=Paper Z=Scissors".to_string()));
+
+        assert_eq!(find_score(&rules, TEST_INPUT.lines(), 1000), Ok(vec![]));
+        assert_eq!(
+            find_score(&rules, "A W".lines(), 0),
+            Err(ParseError::UnknownLetter {
+                line: 1,
+                column: 3,
+                letter: 'W'
+            })
+        );
+    }
+}
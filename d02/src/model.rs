@@ -0,0 +1,323 @@
+use crate::{parse::split_lines, Move, ParseError, Rules};
+use std::fmt;
+
+/// What the opponent's column of a guide says about the opponent: how
+/// often each move is played, and which move follows which.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpponentModel {
+    moves: Vec<Move>,
+    counts: Vec<u64>,
+    /// `transitions[a][b]`: how often `b` directly follows `a`
+    transitions: Vec<Vec<u64>>,
+}
+
+fn probabilities(counts: &[u64]) -> Vec<f64> {
+    let total: u64 = counts.iter().sum();
+    counts
+        .iter()
+        .map(|&count| {
+            if total == 0 {
+                1.0 / counts.len() as f64
+            } else {
+                count as f64 / total as f64
+            }
+        })
+        .collect()
+}
+
+impl OpponentModel {
+    /// The model of the moves in the order they were played, in a game
+    /// with `moves` moves.
+    pub fn new(played: Vec<Move>, moves: usize) -> Self {
+        let mut counts = vec![0; moves];
+        let mut transitions = vec![vec![0; moves]; moves];
+
+        for m in &played {
+            counts[m.0] += 1;
+        }
+        for pair in played.windows(2) {
+            transitions[pair[0].0][pair[1].0] += 1;
+        }
+
+        OpponentModel {
+            moves: played,
+            counts,
+            transitions,
+        }
+    }
+
+    /// The model of the first column of the guide. The second column only
+    /// needs to be well formed.
+    pub fn from_guide<T: AsRef<str>>(
+        rules: &Rules,
+        lines: impl Iterator<Item = T>,
+    ) -> Result<Self, ParseError> {
+        let played = split_lines(lines)
+            .map(|round| {
+                let (other, _) = round?;
+                other.resolve(rules.opponent_move(other.letter))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(OpponentModel::new(played, rules.game.len()))
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Share of every move in the opponent's moves, uniform without moves.
+    pub fn frequencies(&self) -> Vec<f64> {
+        probabilities(&self.counts)
+    }
+
+    /// Share of every move in the moves directly following `previous`,
+    /// [`Self::frequencies`] if `previous` is never followed by anything.
+    pub fn transitions(&self, previous: Move) -> Vec<f64> {
+        if self.transitions[previous.0].iter().all(|&count| count == 0) {
+            self.frequencies()
+        } else {
+            probabilities(&self.transitions[previous.0])
+        }
+    }
+
+    /// The move scoring the most points on average against the opponent
+    /// playing its moves with the given probabilities, and that average.
+    /// Earlier moves of the game win ties.
+    pub fn best_against(rules: &Rules, probabilities: &[f64]) -> (Move, f64) {
+        rules
+            .game
+            .moves()
+            .map(|m| {
+                let expected = rules
+                    .game
+                    .moves()
+                    .map(|other| probabilities[other.0] * rules.score(m, other) as f64)
+                    .sum::<f64>();
+                (m, expected)
+            })
+            .fold(
+                None,
+                |best: Option<(Move, f64)>, (m, expected)| match best {
+                    Some((_, best_expected)) if best_expected >= expected => best,
+                    _ => Some((m, expected)),
+                },
+            )
+            .expect("a game has moves")
+    }
+
+    /// The best single move to play in every round.
+    pub fn best_response(&self, rules: &Rules) -> (Move, f64) {
+        OpponentModel::best_against(rules, &self.frequencies())
+    }
+
+    /// The best move to play after each move of the opponent.
+    pub fn markov_response(&self, rules: &Rules) -> Vec<(Move, f64)> {
+        rules
+            .game
+            .moves()
+            .map(|previous| OpponentModel::best_against(rules, &self.transitions(previous)))
+            .collect()
+    }
+
+    /// Compare both responses with the scores of the guide.
+    pub fn analyze<'a, T: AsRef<str>>(
+        &self,
+        rules: &'a Rules,
+        lines: impl Iterator<Item = T> + Clone,
+    ) -> Result<Analysis<'a>, ParseError> {
+        let best = self.best_response(rules);
+        let markov = self.markov_response(rules);
+
+        // the first round has no previous move, so play the best response
+        let markov_expected = self.moves.first().map_or(0.0, |_| best.1)
+            + self
+                .moves
+                .windows(2)
+                .map(|pair| markov[pair[0].0].1)
+                .sum::<f64>();
+
+        Ok(Analysis {
+            rules,
+            frequencies: self.frequencies(),
+            transitions: rules.game.moves().map(|m| self.transitions(m)).collect(),
+            best,
+            best_expected: best.1 * self.rounds() as f64,
+            markov,
+            markov_expected,
+            part_1: rules.part_1(lines.clone())?,
+            part_2: rules.part_2(lines)?,
+        })
+    }
+}
+
+/// The opponent model of a guide and how well responding to it scores
+/// compared to the guide itself.
+///
+/// The expected scores are for the rounds of the guide, where the model is
+/// exactly right. The best response scores exactly that on the guide. The
+/// markov response can only differ in the first round, which has no
+/// previous move, so it plays the best response there.
+#[derive(Debug)]
+pub struct Analysis<'a> {
+    rules: &'a Rules,
+    pub frequencies: Vec<f64>,
+    /// the probabilities of the next move, after each move
+    pub transitions: Vec<Vec<f64>>,
+    /// the best single move and its expected points per round
+    pub best: (Move, f64),
+    pub best_expected: f64,
+    /// the best move after each move, with its expected points per round
+    pub markov: Vec<(Move, f64)>,
+    pub markov_expected: f64,
+    /// actual scores of the guide
+    pub part_1: u64,
+    pub part_2: u64,
+}
+
+impl fmt::Display for Analysis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let game = &self.rules.game;
+        let shares = |probabilities: &[f64]| {
+            game.moves()
+                .map(|m| format!("{} {:.1}%", game.name(m), 100.0 * probabilities[m.0]))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        writeln!(f, "opponent: {}", shares(&self.frequencies))?;
+        for m in game.moves() {
+            writeln!(
+                f,
+                "  after {}: {}",
+                game.name(m),
+                shares(&self.transitions[m.0])
+            )?;
+        }
+        writeln!(
+            f,
+            "best response: always {}, {:.2} per round, {:.0} expected",
+            game.name(self.best.0),
+            self.best.1,
+            self.best_expected
+        )?;
+        writeln!(f, "markov response: {:.0} expected", self.markov_expected)?;
+        for m in game.moves() {
+            let (reply, expected) = self.markov[m.0];
+            writeln!(
+                f,
+                "  after {}: {}, {:.2} per round",
+                game.name(m),
+                game.name(reply),
+                expected
+            )?;
+        }
+        writeln!(f, "guide: part 1 {}, part 2 {}", self.part_1, self.part_2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Outcome;
+
+    const ROCK: Move = Move(0);
+    const PAPER: Move = Move(1);
+    const SCISSORS: Move = Move(2);
+
+    static TEST_INPUT: &str = "
+        A Y
+        B X
+        C Z";
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-9, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_model() {
+        let model = OpponentModel::new(vec![ROCK, ROCK, PAPER, ROCK, SCISSORS], 3);
+
+        assert_close(&model.frequencies(), &[0.6, 0.2, 0.2]);
+        assert_close(&model.transitions(ROCK), &[1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0]);
+        assert_close(&model.transitions(PAPER), &[1.0, 0.0, 0.0]);
+        // never followed by anything
+        assert_close(&model.transitions(SCISSORS), &model.frequencies());
+    }
+
+    #[test]
+    fn test_empty_model() {
+        let model = OpponentModel::new(vec![], 3);
+        assert_close(&model.frequencies(), &[1.0 / 3.0; 3]);
+    }
+
+    #[test]
+    fn test_best_response() {
+        let rules = Rules::default();
+        let model = OpponentModel::new(vec![ROCK, ROCK, PAPER, ROCK, SCISSORS], 3);
+
+        // paper wins 60% and draws 20%: 2 + 0.6 * 6 + 0.2 * 3
+        let (m, expected) = model.best_response(&rules);
+        assert_eq!(m, PAPER);
+        assert!((expected - 6.2).abs() < 1e-9);
+
+        let markov = model.markov_response(&rules);
+        assert_eq!(markov[PAPER.0].0, PAPER);
+        assert!((markov[PAPER.0].1 - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_expected_is_what_the_responses_score() {
+        let rules = Rules::default();
+        let input = "A X\nA X\nB X\nC X\nA X\nB X\nB X\nC X\nA X";
+        let model = OpponentModel::from_guide(&rules, input.lines()).unwrap();
+        let analysis = model.analyze(&rules, input.lines()).unwrap();
+
+        let played = &model.moves;
+        let best: u64 = played
+            .iter()
+            .map(|&other| rules.score(analysis.best.0, other))
+            .sum();
+        assert!((analysis.best_expected - best as f64).abs() < 1e-9);
+
+        let markov: u64 = played
+            .windows(2)
+            .map(|pair| rules.score(analysis.markov[pair[0].0].0, pair[1]))
+            .sum();
+        assert!((analysis.markov_expected - analysis.best.1 - markov as f64).abs() < 1e-9);
+
+        // the markov response can adapt, so it is never worse
+        assert!(analysis.markov_expected >= analysis.best_expected);
+    }
+
+    #[test]
+    fn test_analyze() {
+        let rules = Rules::default();
+        let model = OpponentModel::from_guide(&rules, TEST_INPUT.lines()).unwrap();
+        let analysis = model.analyze(&rules, TEST_INPUT.lines()).unwrap();
+
+        assert_eq!((analysis.part_1, analysis.part_2), (15, 12));
+        // rock, paper and scissors once each, so scissors with the most
+        // points of its own is best
+        assert_eq!(analysis.best.0, SCISSORS);
+        assert!((analysis.best_expected - 18.0).abs() < 1e-9);
+        // rock is followed by paper and paper by scissors
+        assert_eq!(analysis.markov[ROCK.0].0, rules.choose(PAPER, Outcome::Win));
+        assert!((analysis.markov_expected - (6.0 + 9.0 + 7.0)).abs() < 1e-9);
+        assert!(analysis.to_string().starts_with(
+            "opponent: Rock 33.3%, Paper 33.3%, Scissors 33.3%\n  after Rock: Rock 0.0%, Paper 100.0%"
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        let rules = Rules::default();
+        assert!(OpponentModel::from_guide(&rules, "A X\nD X".lines()).is_err());
+        // the guide is only scored in `analyze`
+        let model = OpponentModel::from_guide(&rules, "A Q".lines()).unwrap();
+        assert!(model.analyze(&rules, "A Q".lines()).is_err());
+    }
+}
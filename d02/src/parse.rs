@@ -0,0 +1,248 @@
+use std::fmt;
+
+/// A character that doesn't stand for anything.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct UnknownLetter(pub char);
+
+impl fmt::Display for UnknownLetter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown letter {:?}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownLetter {}
+
+/// Line and column numbers start at 1 and count characters.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseError {
+    UnknownLetter {
+        line: usize,
+        column: usize,
+        letter: char,
+    },
+    /// `found` is `None` at the end of the line
+    Expected {
+        line: usize,
+        column: usize,
+        expected: &'static str,
+        found: Option<char>,
+    },
+    /// anything but whitespace after the second letter
+    TrailingGarbage {
+        line: usize,
+        column: usize,
+        content: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownLetter {
+                line,
+                column,
+                letter,
+            } => write!(
+                f,
+                "line {}, column {}: unknown letter {:?}",
+                line, column, letter
+            ),
+            ParseError::Expected {
+                line,
+                column,
+                expected,
+                found: Some(found),
+            } => write!(
+                f,
+                "line {}, column {}: expected {}, found {:?}",
+                line, column, expected, found
+            ),
+            ParseError::Expected {
+                line,
+                column,
+                expected,
+                found: None,
+            } => write!(
+                f,
+                "line {}, column {}: expected {}, found the end of the line",
+                line, column, expected
+            ),
+            ParseError::TrailingGarbage {
+                line,
+                column,
+                content,
+            } => write!(
+                f,
+                "line {}, column {}: unexpected {:?} after the round",
+                line, column, content
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A letter of the strategy guide and where it is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Letter {
+    pub letter: char,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Letter {
+    /// `meaning` of the letter, or an error pointing at it.
+    pub fn resolve<T>(self, meaning: Option<T>) -> Result<T, ParseError> {
+        meaning.ok_or(ParseError::UnknownLetter {
+            line: self.line,
+            column: self.column,
+            letter: self.letter,
+        })
+    }
+}
+
+/// Parse a round written as two letters separated by a space, `None` for
+/// empty lines. Surrounding whitespace is ignored.
+pub(crate) fn parse_round(
+    line: &str,
+    number: usize,
+) -> Result<Option<(Letter, Letter)>, ParseError> {
+    let mut chars = line
+        .char_indices()
+        .enumerate()
+        .map(|(i, (offset, c))| (i + 1, offset, c))
+        .skip_while(|&(_, _, c)| c.is_whitespace());
+
+    let letter = |column, letter| Letter {
+        letter,
+        line: number,
+        column,
+    };
+    let expected = |column, expected, found| ParseError::Expected {
+        line: number,
+        column,
+        expected,
+        found,
+    };
+
+    let first = match chars.next() {
+        Some((column, _, c)) => letter(column, c),
+        None => return Ok(None),
+    };
+    match chars.next() {
+        Some((_, _, ' ')) => {}
+        Some((column, _, c)) => return Err(expected(column, "a space", Some(c))),
+        None => return Err(expected(first.column + 1, "a space", None)),
+    }
+    let second = match chars.next() {
+        Some((column, _, c)) if !c.is_whitespace() => letter(column, c),
+        Some((column, _, c)) => return Err(expected(column, "a letter", Some(c))),
+        None => return Err(expected(first.column + 2, "a letter", None)),
+    };
+
+    if let Some((column, offset, _)) = chars.find(|&(_, _, c)| !c.is_whitespace()) {
+        return Err(ParseError::TrailingGarbage {
+            line: number,
+            column,
+            content: line[offset..].trim_end().to_string(),
+        });
+    }
+
+    Ok(Some((first, second)))
+}
+
+/// The rounds of the guide, skipping empty lines.
+pub(crate) fn split_lines<T: AsRef<str>>(
+    lines: impl Iterator<Item = T>,
+) -> impl Iterator<Item = Result<(Letter, Letter), ParseError>> {
+    lines
+        .enumerate()
+        .filter_map(|(i, line)| parse_round(line.as_ref(), i + 1).transpose())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn letters(round: (Letter, Letter)) -> (char, usize, char, usize) {
+        (
+            round.0.letter,
+            round.0.column,
+            round.1.letter,
+            round.1.column,
+        )
+    }
+
+    #[test_case("A Y", ('A', 1, 'Y', 3))]
+    #[test_case("    B X", ('B', 5, 'X', 7); "indented")]
+    #[test_case("C Z\r", ('C', 1, 'Z', 3); "crlf")]
+    #[test_case("C Z  \t", ('C', 1, 'Z', 3); "trailing whitespace")]
+    #[test_case("ä ö", ('ä', 1, 'ö', 3); "columns count characters")]
+    fn test_parse_round(line: &str, expected: (char, usize, char, usize)) {
+        assert_eq!(
+            parse_round(line, 1).map(|round| round.map(letters)),
+            Ok(Some(expected))
+        );
+    }
+
+    #[test]
+    fn test_empty_lines() {
+        assert_eq!(parse_round("", 1), Ok(None));
+        assert_eq!(parse_round("  \t", 1), Ok(None));
+    }
+
+    #[test_case("AY", 2, "a space", Some('Y'))]
+    #[test_case("A", 2, "a space", None)]
+    #[test_case("A\tY", 2, "a space", Some('\t'))]
+    #[test_case("A  Y", 3, "a letter", Some(' '))]
+    #[test_case("A ", 3, "a letter", None)]
+    fn test_expected(line: &str, column: usize, expected: &'static str, found: Option<char>) {
+        assert_eq!(
+            parse_round(line, 4),
+            Err(ParseError::Expected {
+                line: 4,
+                column,
+                expected,
+                found
+            })
+        );
+    }
+
+    #[test]
+    fn test_trailing_garbage() {
+        assert_eq!(
+            parse_round("  A YZ x ", 2),
+            Err(ParseError::TrailingGarbage {
+                line: 2,
+                column: 6,
+                content: "Z x".into()
+            })
+        );
+        assert_eq!(
+            parse_round("A Y # comment", 2),
+            Err(ParseError::TrailingGarbage {
+                line: 2,
+                column: 5,
+                content: "# comment".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_split_lines_counts_empty_lines() {
+        let rounds: Vec<_> = split_lines("\nA Y\n\nB".lines()).collect();
+
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].as_ref().map(|r| r.0.line), Ok(2));
+        assert_eq!(
+            rounds[1],
+            Err(ParseError::Expected {
+                line: 4,
+                column: 2,
+                expected: "a space",
+                found: None
+            })
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{Move, Outcome, ParseError, Part, Rules};
+use std::{fmt, io};
+
+/// A scored round of a [`Report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundReport {
+    /// line of the round in the guide, starting at 1
+    pub line: usize,
+    pub other_move: Move,
+    pub my_move: Move,
+    pub outcome: Outcome,
+    pub shape_points: u64,
+    pub outcome_points: u64,
+    /// score of all rounds up to and including this one
+    pub total: u64,
+}
+
+/// Round by round breakdown of how a guide is scored.
+#[derive(Debug)]
+pub struct Report<'a> {
+    rules: &'a Rules,
+    pub part: Part,
+    pub rounds: Vec<RoundReport>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new<T: AsRef<str>>(
+        rules: &'a Rules,
+        part: Part,
+        lines: impl Iterator<Item = T>,
+    ) -> Result<Self, ParseError> {
+        let mut total = 0;
+        let rounds = rules
+            .rounds(part, lines)
+            .map(|round| {
+                let round = round?;
+                let outcome = rules.game.play(round.my_move, round.other_move);
+                let shape_points = rules.move_points(round.my_move);
+                let outcome_points = rules.outcome_points(outcome);
+                total += shape_points + outcome_points;
+
+                Ok(RoundReport {
+                    line: round.line,
+                    other_move: round.other_move,
+                    my_move: round.my_move,
+                    outcome,
+                    shape_points,
+                    outcome_points,
+                    total,
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        Ok(Report {
+            rules,
+            part,
+            rounds,
+        })
+    }
+
+    pub fn total(&self) -> u64 {
+        self.rounds.last().map_or(0, |round| round.total)
+    }
+
+    /// Write the rounds as CSV with a header line.
+    pub fn write_csv(&self, mut w: impl io::Write) -> io::Result<()> {
+        writeln!(
+            w,
+            "line,opponent,me,outcome,shape_points,outcome_points,total"
+        )?;
+        for round in &self.rounds {
+            writeln!(
+                w,
+                "{},{},{},{},{},{},{}",
+                round.line,
+                self.rules.game.name(round.other_move),
+                self.rules.game.name(round.my_move),
+                round.outcome,
+                round.shape_points,
+                round.outcome_points,
+                round.total
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let game = &self.rules.game;
+        let width = game.moves().map(|m| game.name(m).len()).max().unwrap_or(0);
+
+        match self.part {
+            Part::One => writeln!(f, "part 1, the second column is my move")?,
+            Part::Two => writeln!(f, "part 2, the second column is the outcome")?,
+        }
+        writeln!(
+            f,
+            "{:>5}  {:<width$}  {:<width$}  {:<7}  {:>5}  {:>7}  {:>7}",
+            "line",
+            "opponent",
+            "me",
+            "outcome",
+            "shape",
+            "outcome",
+            "total",
+            width = width.max("opponent".len())
+        )?;
+        for round in &self.rounds {
+            writeln!(
+                f,
+                "{:>5}  {:<width$}  {:<width$}  {:<7}  {:>5}  {:>7}  {:>7}",
+                round.line,
+                game.name(round.other_move),
+                game.name(round.my_move),
+                round.outcome.to_string(),
+                round.shape_points,
+                round.outcome_points,
+                round.total,
+                width = width.max("opponent".len())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = "
+        A Y
+        B X
+        C Z";
+
+    #[test]
+    fn test_report() {
+        let rules = Rules::default();
+
+        for part in [Part::One, Part::Two] {
+            let report = Report::new(&rules, part, TEST_INPUT.lines()).unwrap();
+            assert_eq!(Ok(report.total()), rules.total(part, TEST_INPUT.lines()));
+        }
+
+        let report = Report::new(&rules, Part::Two, TEST_INPUT.lines()).unwrap();
+        assert_eq!(
+            report.rounds[1],
+            RoundReport {
+                line: 3,
+                other_move: Move(1),
+                my_move: Move(0),
+                outcome: Outcome::Lose,
+                shape_points: 1,
+                outcome_points: 0,
+                total: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let rules = Rules::default();
+        let report = Report::new(&rules, Part::One, TEST_INPUT.lines()).unwrap();
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "line,opponent,me,outcome,shape_points,outcome_points,total
+2,Rock,Paper,win,2,6,8
+3,Paper,Rock,lose,1,0,9
+4,Scissors,Scissors,draw,3,3,15
+"
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let rules = Rules::default();
+        let report = Report::new(&rules, Part::Two, "A Y".lines()).unwrap();
+
+        assert_eq!(
+            report.to_string(),
+            "part 2, the second column is the outcome
+ line  opponent  me        outcome  shape  outcome    total
+    1  Rock      Rock      draw         1        3        4
+"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let rules = Rules::default();
+        assert!(Report::new(&rules, Part::One, "A Y\nB".lines()).is_err());
+    }
+}
@@ -0,0 +1,424 @@
+use crate::{parse::split_lines, Game, GameError, Move, Outcome, ParseError, RPC};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt, path::Path};
+
+#[derive(Debug)]
+pub enum RulesError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    /// the file extension is neither `toml` nor `json`
+    UnknownFormat(String),
+    Game(GameError),
+    /// a move without points in the scoring scheme
+    MissingPoints(String),
+    /// a key of a letter mapping that isn't a single character
+    InvalidLetter(String),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::Io(err) => write!(f, "could not read the rules: {}", err),
+            RulesError::Toml(err) => write!(f, "invalid toml rules: {}", err),
+            RulesError::Json(err) => write!(f, "invalid json rules: {}", err),
+            RulesError::UnknownFormat(path) => {
+                write!(f, "rules must be a .toml or .json file, got {:?}", path)
+            }
+            RulesError::Game(err) => err.fmt(f),
+            RulesError::MissingPoints(name) => write!(f, "no points for move {:?}", name),
+            RulesError::InvalidLetter(letter) => {
+                write!(f, "letters must be single characters, got {:?}", letter)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RulesError {}
+
+impl From<GameError> for RulesError {
+    fn from(err: GameError) -> Self {
+        RulesError::Game(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum GameKind {
+    #[default]
+    Rps,
+    Rpsls,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct OutcomePoints {
+    pub win: u64,
+    pub draw: u64,
+    pub lose: u64,
+}
+
+/// How the second column of the guide is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    /// my move
+    One,
+    /// the wanted outcome
+    Two,
+}
+
+/// A round of the guide, see [`Rules::rounds`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    /// line of the round in the guide, starting at 1
+    pub line: usize,
+    pub other_move: Move,
+    pub my_move: Move,
+}
+
+/// The rules as written in a file, everything left out is the default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RulesFile {
+    game: GameKind,
+    /// move name -> points for playing it
+    points: Option<BTreeMap<String, u64>>,
+    outcome_points: Option<OutcomePoints>,
+    /// letter -> move name, for the first column
+    opponent: Option<BTreeMap<String, String>>,
+    /// letter -> move name, for the second column in part 1
+    me: Option<BTreeMap<String, String>>,
+    /// letter -> outcome, for the second column in part 2
+    outcomes: Option<BTreeMap<String, Outcome>>,
+}
+
+/// How a strategy guide is read and scored.
+///
+/// [`Rules::default`] are the rules of the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub game: Game,
+    move_points: Vec<u64>,
+    pub outcome_points: OutcomePoints,
+    opponent: BTreeMap<char, Move>,
+    me: BTreeMap<char, Move>,
+    outcomes: BTreeMap<char, Outcome>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        let game = Game::rps();
+        let known = "every letter of the puzzle is known";
+        let moves = |letters: &str| {
+            letters
+                .chars()
+                .map(|c| (c, RPC::try_from(c).expect(known).into()))
+                .collect()
+        };
+
+        Rules {
+            move_points: game.moves().map(|m| game.points(m)).collect(),
+            game,
+            outcome_points: OutcomePoints {
+                win: Outcome::Win.points(),
+                draw: Outcome::Draw.points(),
+                lose: Outcome::Lose.points(),
+            },
+            opponent: moves("ABC"),
+            me: moves("XYZ"),
+            outcomes: "XYZ"
+                .chars()
+                .map(|c| (c, Outcome::try_from(c).expect(known)))
+                .collect(),
+        }
+    }
+}
+
+fn letters<T, U>(
+    map: BTreeMap<String, T>,
+    mut convert: impl FnMut(T) -> Result<U, RulesError>,
+) -> Result<BTreeMap<char, U>, RulesError> {
+    map.into_iter()
+        .map(|(letter, value)| {
+            let mut chars = letter.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok((c, convert(value)?)),
+                _ => Err(RulesError::InvalidLetter(letter)),
+            }
+        })
+        .collect()
+}
+
+impl Rules {
+    pub fn from_toml(s: &str) -> Result<Rules, RulesError> {
+        Rules::from_file(toml::from_str(s).map_err(RulesError::Toml)?)
+    }
+
+    pub fn from_json(s: &str) -> Result<Rules, RulesError> {
+        Rules::from_file(serde_json::from_str(s).map_err(RulesError::Json)?)
+    }
+
+    /// Load the rules from a `.toml` or `.json` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Rules, RulesError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(RulesError::Io)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Rules::from_toml(&content),
+            Some("json") => Rules::from_json(&content),
+            _ => Err(RulesError::UnknownFormat(path.display().to_string())),
+        }
+    }
+
+    fn from_file(file: RulesFile) -> Result<Rules, RulesError> {
+        let mut rules = Rules {
+            game: match file.game {
+                GameKind::Rps => Game::rps(),
+                GameKind::Rpsls => Game::rpsls(),
+            },
+            ..Rules::default()
+        };
+        let game = &rules.game;
+
+        rules.move_points = match file.points {
+            Some(points) => game
+                .moves()
+                .map(|m| {
+                    points
+                        .get(game.name(m))
+                        .copied()
+                        .ok_or_else(|| RulesError::MissingPoints(game.name(m).into()))
+                })
+                .collect::<Result<_, _>>()?,
+            None => game.moves().map(|m| game.points(m)).collect(),
+        };
+        if let Some(outcome_points) = file.outcome_points {
+            rules.outcome_points = outcome_points;
+        }
+        if let Some(opponent) = file.opponent {
+            rules.opponent = letters(opponent, |name| Ok(game.find(&name)?))?;
+        }
+        if let Some(me) = file.me {
+            rules.me = letters(me, |name| Ok(game.find(&name)?))?;
+        }
+        if let Some(outcomes) = file.outcomes {
+            rules.outcomes = letters(outcomes, Ok)?;
+        }
+
+        Ok(rules)
+    }
+
+    pub fn move_points(&self, m: Move) -> u64 {
+        self.move_points[m.0]
+    }
+
+    pub fn outcome_points(&self, outcome: Outcome) -> u64 {
+        match outcome {
+            Outcome::Win => self.outcome_points.win,
+            Outcome::Draw => self.outcome_points.draw,
+            Outcome::Lose => self.outcome_points.lose,
+        }
+    }
+
+    pub fn opponent_move(&self, letter: char) -> Option<Move> {
+        self.opponent.get(&letter).copied()
+    }
+
+    pub fn my_move(&self, letter: char) -> Option<Move> {
+        self.me.get(&letter).copied()
+    }
+
+    pub fn outcome(&self, letter: char) -> Option<Outcome> {
+        self.outcomes.get(&letter).copied()
+    }
+
+    /// My move for the second column `letter` against `other_move`,
+    /// reading it as `part` does.
+    pub fn second_move(&self, part: Part, other_move: Move, letter: char) -> Option<Move> {
+        match part {
+            Part::One => self.my_move(letter),
+            Part::Two => self
+                .outcome(letter)
+                .map(|outcome| self.choose(other_move, outcome)),
+        }
+    }
+
+    /// The letters the first column can have.
+    pub fn opponent_letters(&self) -> Vec<char> {
+        self.opponent.keys().copied().collect()
+    }
+
+    /// The letters the second column can have, reading it as `part` does.
+    pub fn second_letters(&self, part: Part) -> Vec<char> {
+        match part {
+            Part::One => self.me.keys().copied().collect(),
+            Part::Two => self.outcomes.keys().copied().collect(),
+        }
+    }
+
+    /// Points for a round where `my_move` is played against `other_move`.
+    pub fn score(&self, my_move: Move, other_move: Move) -> u64 {
+        self.outcome_points(self.game.play(my_move, other_move)) + self.move_points(my_move)
+    }
+
+    /// The move for `outcome` against `other_move` scoring the most points.
+    pub fn choose(&self, other_move: Move, outcome: Outcome) -> Move {
+        self.game
+            .moves_for(other_move, outcome)
+            .max_by_key(|&m| self.move_points(m))
+            .expect("every outcome is possible in a balanced game")
+    }
+
+    /// The rounds of the guide, reading the second column as `part` does.
+    pub fn rounds<'a, T: AsRef<str> + 'a>(
+        &'a self,
+        part: Part,
+        lines: impl Iterator<Item = T> + 'a,
+    ) -> impl Iterator<Item = Result<Round, ParseError>> + 'a {
+        split_lines(lines).map(move |round| {
+            let (other_letter, second) = round?;
+            let other_move = other_letter.resolve(self.opponent_move(other_letter.letter))?;
+            let my_move = second.resolve(self.second_move(part, other_move, second.letter))?;
+
+            Ok(Round {
+                line: other_letter.line,
+                other_move,
+                my_move,
+            })
+        })
+    }
+
+    /// Total score of the guide, reading the second column as `part` does.
+    pub fn total<T: AsRef<str>>(
+        &self,
+        part: Part,
+        lines: impl Iterator<Item = T>,
+    ) -> Result<u64, ParseError> {
+        self.rounds(part, lines)
+            .map(|round| round.map(|round| self.score(round.my_move, round.other_move)))
+            .sum()
+    }
+
+    /// Total score when the second column is my move.
+    pub fn part_1<T: AsRef<str>>(&self, lines: impl Iterator<Item = T>) -> Result<u64, ParseError> {
+        self.total(Part::One, lines)
+    }
+
+    /// Total score when the second column is the wanted outcome.
+    pub fn part_2<T: AsRef<str>>(&self, lines: impl Iterator<Item = T>) -> Result<u64, ParseError> {
+        self.total(Part::Two, lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_INPUT: &str = "
+        A Y
+        B X
+        C Z";
+
+    #[test]
+    fn test_default_is_the_puzzle() {
+        let rules = Rules::default();
+
+        assert_eq!(rules.part_1(TEST_INPUT.lines()), Ok(15));
+        assert_eq!(rules.part_2(TEST_INPUT.lines()), Ok(12));
+        assert_eq!(Rules::from_toml("").unwrap(), rules);
+        assert_eq!(Rules::from_json("{}").unwrap(), rules);
+    }
+
+    #[test]
+    fn test_toml() {
+        let rules = Rules::from_toml(
+            r#"
+            outcome_points = { win = 1, draw = 0, lose = 0 }
+
+            [points]
+            Rock = 0
+            Paper = 0
+            Scissors = 0
+
+            [me]
+            X = "Paper"
+            Y = "Rock"
+            Z = "Scissors"
+            "#,
+        )
+        .unwrap();
+
+        // only wins count, and every round of the example is a draw now
+        assert_eq!(rules.part_1(TEST_INPUT.lines()), Ok(0));
+        assert_eq!(rules.part_1("A X\nC Y".lines()), Ok(2));
+        // the outcomes are still the puzzle ones
+        assert_eq!(rules.part_2(TEST_INPUT.lines()), Ok(1));
+    }
+
+    #[test]
+    fn test_json_rpsls() {
+        let rules = Rules::from_json(
+            r#"{
+                "game": "rpsls",
+                "opponent": { "A": "Rock", "B": "Spock" },
+                "outcomes": { "L": "lose", "W": "win" }
+            }"#,
+        )
+        .unwrap();
+
+        // Spock (4) vaporizes Rock, Lizard (5) poisons Spock
+        assert_eq!(rules.part_2("A W\nB W".lines()), Ok(6 + 4 + 6 + 5));
+        // Lizard (5) loses to Rock, Scissors (3) loses to Spock
+        assert_eq!(rules.part_2("A L\nB L".lines()), Ok(5 + 3));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Rules::from_toml("[points]\nRock = 1"),
+            Err(RulesError::MissingPoints(name)) if name == "Paper"
+        ));
+        assert!(matches!(
+            Rules::from_toml("[me]\nX = \"Spock\""),
+            Err(RulesError::Game(GameError::UnknownMove(name))) if name == "Spock"
+        ));
+        assert!(matches!(
+            Rules::from_toml("[me]\nXY = \"Rock\""),
+            Err(RulesError::InvalidLetter(letter)) if letter == "XY"
+        ));
+        assert!(matches!(
+            Rules::from_toml("scoring = 1"),
+            Err(RulesError::Toml(_))
+        ));
+        assert!(matches!(
+            Rules::from_json(r#"{"outcomes": {"X": "tie"}}"#),
+            Err(RulesError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.json");
+        std::fs::write(
+            &path,
+            r#"{"outcome_points": {"win": 0, "draw": 0, "lose": 0}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Rules::load(&path).unwrap().part_1(TEST_INPUT.lines()),
+            Ok(6)
+        );
+        assert!(matches!(
+            Rules::load(dir.path().join("rules.yaml")),
+            Err(RulesError::Io(_))
+        ));
+
+        let path = dir.path().join("rules.yaml");
+        std::fs::write(&path, "").unwrap();
+        assert!(matches!(
+            Rules::load(&path),
+            Err(RulesError::UnknownFormat(_))
+        ));
+    }
+}
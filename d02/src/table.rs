@@ -0,0 +1,266 @@
+use crate::{parse::parse_round, ParseError, Part, Rules, UnknownLetter};
+
+const OPPONENT: [u8; 3] = *b"ABC";
+const SECOND: [u8; 3] = *b"XYZ";
+
+/// The score of every possible round, for scoring huge guides straight
+/// from their bytes.
+///
+/// A round `A Y` is at index `3 * 0 + 1`, so a line is scored by looking at
+/// two bytes and a single load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreTable {
+    scores: [u64; 9],
+}
+
+impl ScoreTable {
+    /// The table of the `part` score under `rules`, which must give a meaning
+    /// to every letter of `A`, `B`, `C` and `X`, `Y`, `Z`.
+    pub fn new(rules: &Rules, part: Part) -> Result<ScoreTable, UnknownLetter> {
+        let mut scores = [0; 9];
+
+        for (i, &other) in OPPONENT.iter().enumerate() {
+            let other = other as char;
+            let other_move = rules.opponent_move(other).ok_or(UnknownLetter(other))?;
+
+            for (j, &second) in SECOND.iter().enumerate() {
+                let second = second as char;
+                let my_move = rules
+                    .second_move(part, other_move, second)
+                    .ok_or(UnknownLetter(second))?;
+
+                scores[3 * i + j] = rules.score(my_move, other_move);
+            }
+        }
+
+        Ok(ScoreTable { scores })
+    }
+
+    /// The table of the `part` score under `rules`, if the rules know
+    /// exactly the letters above. Rules with more letters would have rounds
+    /// the table rejects, so they need [`Rules::total`].
+    pub fn for_rules(rules: &Rules, part: Part) -> Option<ScoreTable> {
+        let exactly = |letters: Vec<char>, expected: &[u8]| {
+            letters
+                .iter()
+                .copied()
+                .eq(expected.iter().map(|&b| b as char))
+        };
+        if exactly(rules.opponent_letters(), &OPPONENT)
+            && exactly(rules.second_letters(part), &SECOND)
+        {
+            ScoreTable::new(rules, part).ok()
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn lookup(&self, other: u8, second: u8) -> Option<u64> {
+        let i = other.wrapping_sub(b'A');
+        let j = second.wrapping_sub(b'X');
+        if i < 3 && j < 3 {
+            Some(self.scores[3 * i as usize + j as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Total score of the guide, with the same results and errors as
+    /// [`Rules::total`] for the rules the table was built from. Letters
+    /// other than the ones above are unknown, even if the rules know them.
+    pub fn score(&self, input: &[u8]) -> Result<u64, ParseError> {
+        let mut total = 0;
+        let mut rest = input;
+        let mut number = 0;
+
+        while !rest.is_empty() {
+            // almost every line is `A Y\n`, only search for other lengths
+            let end = if rest.len() > 3 && rest[3] == b'\n' {
+                3
+            } else {
+                memchr::memchr(b'\n', rest).unwrap_or(rest.len())
+            };
+            let line = &rest[..end];
+            rest = &rest[(end + 1).min(rest.len())..];
+            number += 1;
+
+            let score = match line {
+                [other, b' ', second] | [other, b' ', second, b'\r'] => {
+                    self.lookup(*other, *second)
+                }
+                _ => None,
+            };
+            total += match score {
+                Some(score) => score,
+                None => self.score_slow(line, number)?,
+            };
+        }
+
+        Ok(total)
+    }
+
+    /// Everything that isn't a plain round of known letters, such as empty
+    /// or indented lines and errors.
+    #[cold]
+    fn score_slow(&self, line: &[u8], number: usize) -> Result<u64, ParseError> {
+        let line = String::from_utf8_lossy(line);
+
+        match parse_round(&line, number)? {
+            None => Ok(0),
+            Some((other, second)) => {
+                // only single byte letters can be known
+                let byte = |c: char| u8::try_from(c).unwrap_or(0);
+                let i = other.resolve(OPPONENT.iter().position(|&b| b == byte(other.letter)))?;
+                let j = second.resolve(SECOND.iter().position(|&b| b == byte(second.letter)))?;
+
+                Ok(self.scores[3 * i + j])
+            }
+        }
+    }
+}
+
+/// Total `part` score of the guide under `rules`, from a [`ScoreTable`] when
+/// the rules allow one and from [`Rules::total`] otherwise.
+pub fn fast_total(rules: &Rules, part: Part, input: &str) -> Result<u64, ParseError> {
+    match ScoreTable::for_rules(rules, part) {
+        Some(table) => table.score(input.as_bytes()),
+        None => rules.total(part, input.lines()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn check(rules: &Rules, input: &str) {
+        for part in [Part::One, Part::Two] {
+            let table = ScoreTable::new(rules, part).unwrap();
+            assert_eq!(
+                table.score(input.as_bytes()),
+                rules.total(part, input.lines()),
+                "{:?} of {:?}",
+                part,
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_round() {
+        let rules = Rules::default();
+
+        for other in OPPONENT {
+            for second in SECOND {
+                check(&rules, &format!("{} {}\n", other as char, second as char));
+            }
+        }
+    }
+
+    #[test_case("A Y\nB X\nC Z"; "example")]
+    #[test_case("A Y\r\nB X\r\nC Z\r\n"; "crlf")]
+    #[test_case("\n  A Y\n\nB X  \n\t\nC Z\n\n"; "whitespace")]
+    #[test_case(""; "empty")]
+    #[test_case("A Y\nB Q\nC Z"; "unknown letter")]
+    #[test_case("A Y\nD X"; "unknown opponent letter")]
+    #[test_case("A Y\nB X Z\n"; "trailing garbage")]
+    #[test_case("A Y\nBX\n"; "missing space")]
+    #[test_case("A Y\nä X\n"; "non ascii letter")]
+    fn test_same_as_rules(input: &str) {
+        check(&Rules::default(), input);
+    }
+
+    #[test]
+    fn test_other_rules() {
+        let rules = Rules::from_toml(
+            r#"
+            game = "rpsls"
+            outcome_points = { win = 10, draw = 1, lose = 0 }
+
+            [opponent]
+            A = "Spock"
+            B = "Lizard"
+            C = "Rock"
+
+            [me]
+            X = "Lizard"
+            Y = "Rock"
+            Z = "Spock"
+            "#,
+        )
+        .unwrap();
+
+        check(&rules, "A Y\nB X\nC Z\nA X\nB Z\nC Y\n");
+    }
+
+    #[test]
+    fn test_many_rounds() {
+        let mut seed = 7u64;
+        let input: String = (0..10_000)
+            .map(|_| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                let round = (seed >> 33) as usize % 9;
+                format!(
+                    "{} {}\n",
+                    OPPONENT[round / 3] as char,
+                    SECOND[round % 3] as char
+                )
+            })
+            .collect();
+
+        check(&Rules::default(), &input);
+    }
+
+    #[test]
+    fn test_more_letters() {
+        let rules = Rules::from_toml(
+            r#"
+            game = "rpsls"
+
+            [opponent]
+            A = "Rock"
+            B = "Paper"
+            C = "Scissors"
+            D = "Spock"
+            E = "Lizard"
+
+            [me]
+            X = "Rock"
+            Y = "Paper"
+            Z = "Scissors"
+            V = "Spock"
+            W = "Lizard"
+            "#,
+        )
+        .unwrap();
+        let input = "A Y\nD V\nE W\nC Z\nB X\n";
+
+        assert_eq!(ScoreTable::for_rules(&rules, Part::One), None);
+        assert_eq!(ScoreTable::for_rules(&rules, Part::Two), None);
+        // Paper covers Rock, Spock draws, Lizard draws, Scissors draws, Rock
+        // loses to Paper
+        assert_eq!(
+            fast_total(&rules, Part::One, input),
+            Ok((2 + 6) + (4 + 3) + (5 + 3) + (3 + 3) + 1)
+        );
+        assert_eq!(
+            fast_total(&rules, Part::Two, "A Y\nD X\nE Z\n"),
+            rules.total(Part::Two, "A Y\nD X\nE Z\n".lines())
+        );
+    }
+
+    #[test]
+    fn test_fast_total() {
+        let rules = Rules::default();
+        assert!(ScoreTable::for_rules(&rules, Part::Two).is_some());
+        assert_eq!(fast_total(&rules, Part::One, "A Y\nB X\nC Z"), Ok(15));
+        assert_eq!(fast_total(&rules, Part::Two, "A Y\nB X\nC Z"), Ok(12));
+    }
+
+    #[test]
+    fn test_unknown_letters() {
+        let rules = Rules::from_toml("[opponent]\nA = \"Rock\"").unwrap();
+        assert_eq!(ScoreTable::new(&rules, Part::One), Err(UnknownLetter('B')));
+    }
+}
@@ -0,0 +1,529 @@
+use crate::{Move, Outcome, ParseError, Part, Rules};
+use std::{collections::HashMap, fmt};
+
+/// A player of a tournament.
+///
+/// Strategies are reset before every match, so a match doesn't depend on
+/// the ones played before.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// Forget everything about earlier matches.
+    fn reset(&mut self) {}
+
+    /// The move for the next round, `history` holds my and the opponent's
+    /// moves of the earlier rounds.
+    fn next_move(&mut self, rules: &Rules, history: &[(Move, Move)]) -> Move;
+}
+
+/// A strategy guide that can't be played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuideError {
+    Parse(ParseError),
+    /// the guide has no rounds
+    Empty,
+}
+
+impl fmt::Display for GuideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuideError::Parse(err) => err.fmt(f),
+            GuideError::Empty => write!(f, "the guide has no rounds"),
+        }
+    }
+}
+
+impl std::error::Error for GuideError {}
+
+impl From<ParseError> for GuideError {
+    fn from(err: ParseError) -> Self {
+        GuideError::Parse(err)
+    }
+}
+
+/// Plays the moves of a strategy guide in order, starting over when the
+/// match is longer than the guide.
+pub struct FixedGuide {
+    moves: Vec<Move>,
+}
+
+impl FixedGuide {
+    /// Panics without any moves, see [`FixedGuide::from_guide`] for guides
+    /// read from the input.
+    pub fn new(moves: Vec<Move>) -> Self {
+        assert!(!moves.is_empty(), "a guide needs at least one move");
+        FixedGuide { moves }
+    }
+
+    /// My moves of the guide, reading the second column as in part 1.
+    pub fn from_guide<T: AsRef<str>>(
+        rules: &Rules,
+        lines: impl Iterator<Item = T>,
+    ) -> Result<Self, GuideError> {
+        let moves = rules
+            .rounds(Part::One, lines)
+            .map(|round| round.map(|round| round.my_move))
+            .collect::<Result<Vec<_>, _>>()?;
+        if moves.is_empty() {
+            return Err(GuideError::Empty);
+        }
+        Ok(FixedGuide::new(moves))
+    }
+}
+
+impl Strategy for FixedGuide {
+    fn name(&self) -> String {
+        "guide".into()
+    }
+
+    fn next_move(&mut self, _: &Rules, history: &[(Move, Move)]) -> Move {
+        self.moves[history.len() % self.moves.len()]
+    }
+}
+
+/// splitmix64, good enough for picking moves and reproducible everywhere.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Picks uniformly random moves, the same ones for the same seed.
+pub struct Random {
+    seed: u64,
+    rng: Rng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random {
+            seed,
+            rng: Rng(seed),
+        }
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        format!("random({})", self.seed)
+    }
+
+    fn reset(&mut self) {
+        self.rng = Rng(self.seed);
+    }
+
+    fn next_move(&mut self, rules: &Rules, _: &[(Move, Move)]) -> Move {
+        Move(self.rng.next() as usize % rules.game.len())
+    }
+}
+
+/// The move beating the most frequent move of the counts, where ties go to
+/// the earlier move of the game. Without any moves, the first move.
+fn beat_most_frequent(rules: &Rules, counts: &[u64]) -> Move {
+    // `max_by_key` keeps the last maximum, so go backwards
+    match counts
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, &count)| count)
+    {
+        Some((m, &count)) if count > 0 => rules.choose(Move(m), Outcome::Win),
+        _ => Move(0),
+    }
+}
+
+/// The opponent's moves of a match, taken from the history one round at a
+/// time so every round only counts the new move. Checking that the history
+/// still starts with the known moves reads them all, but that's a plain
+/// comparison instead of counting again.
+#[derive(Debug, Default)]
+struct Opponent {
+    moves: Vec<Move>,
+    counts: Vec<u64>,
+}
+
+impl Opponent {
+    /// The moves not seen yet, starting over for a history that isn't the
+    /// continuation of the one before.
+    fn update<'h>(&mut self, rules: &Rules, history: &'h [(Move, Move)]) -> &'h [(Move, Move)] {
+        let continues = history.len() >= self.moves.len()
+            && history
+                .iter()
+                .zip(&self.moves)
+                .all(|(&(_, other), &seen)| other == seen);
+        if !continues || self.counts.len() != rules.game.len() {
+            self.moves.clear();
+            self.counts = vec![0; rules.game.len()];
+        }
+
+        let new = &history[self.moves.len()..];
+        for &(_, other) in new {
+            self.moves.push(other);
+            self.counts[other.0] += 1;
+        }
+        new
+    }
+}
+
+/// Counts the opponent's moves and beats the most frequent one.
+#[derive(Default)]
+pub struct FrequencyCounter {
+    opponent: Opponent,
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency".into()
+    }
+
+    fn reset(&mut self) {
+        self.opponent = Opponent::default();
+    }
+
+    fn next_move(&mut self, rules: &Rules, history: &[(Move, Move)]) -> Move {
+        self.opponent.update(rules, history);
+        beat_most_frequent(rules, &self.opponent.counts)
+    }
+}
+
+/// Looks for the opponent's last `depth` moves earlier in the match and
+/// beats the move that followed them most often. Without a match, falls
+/// back to the [`FrequencyCounter`].
+pub struct PatternPredictor {
+    depth: usize,
+    opponent: Opponent,
+    /// how often every move followed each run of `depth` moves
+    followers: HashMap<Vec<Move>, Vec<u64>>,
+}
+
+impl PatternPredictor {
+    pub fn new(depth: usize) -> Self {
+        assert!(depth > 0, "the pattern needs at least one move");
+        PatternPredictor {
+            depth,
+            opponent: Opponent::default(),
+            followers: HashMap::new(),
+        }
+    }
+}
+
+impl Strategy for PatternPredictor {
+    fn name(&self) -> String {
+        format!("pattern({})", self.depth)
+    }
+
+    fn reset(&mut self) {
+        self.opponent = Opponent::default();
+        self.followers.clear();
+    }
+
+    fn next_move(&mut self, rules: &Rules, history: &[(Move, Move)]) -> Move {
+        let before = self.opponent.moves.len();
+        let new = self.opponent.update(rules, history).len();
+        if self.opponent.moves.len() != before + new {
+            // started over
+            self.followers.clear();
+        }
+
+        let moves = &self.opponent.moves;
+        for end in (moves.len() - new).max(self.depth)..moves.len() {
+            let counts = self
+                .followers
+                .entry(moves[end - self.depth..end].to_vec())
+                .or_insert_with(|| vec![0; rules.game.len()]);
+            counts[moves[end].0] += 1;
+        }
+
+        if moves.len() <= self.depth {
+            return beat_most_frequent(rules, &self.opponent.counts);
+        }
+        let pattern = &moves[moves.len() - self.depth..];
+        match self.followers.get(pattern) {
+            Some(counts) => beat_most_frequent(rules, counts),
+            None => beat_most_frequent(rules, &self.opponent.counts),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    /// matches won, drawn and lost, decided by the rounds won
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// points of all rounds of all matches
+    pub score: u64,
+}
+
+/// The strategies by score, most points first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standings(pub Vec<Standing>);
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.0.iter().map(|s| s.name.len()).max().unwrap_or(0);
+        let width = width.max("strategy".len());
+
+        writeln!(
+            f,
+            "{:<width$}  {:>4}  {:>4}  {:>4}  {:>8}",
+            "strategy", "won", "draw", "lost", "score"
+        )?;
+        for s in &self.0 {
+            writeln!(
+                f,
+                "{:<width$}  {:>4}  {:>4}  {:>4}  {:>8}",
+                s.name, s.wins, s.draws, s.losses, s.score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Play a match of `rounds` rounds, returning the points of `a` and `b`
+/// and the rounds each of them won.
+fn play_match(
+    rules: &Rules,
+    a: &mut dyn Strategy,
+    b: &mut dyn Strategy,
+    rounds: usize,
+) -> ((u64, usize), (u64, usize)) {
+    a.reset();
+    b.reset();
+    let mut history_a = Vec::with_capacity(rounds);
+    let mut history_b = Vec::with_capacity(rounds);
+    let (mut result_a, mut result_b) = ((0, 0), (0, 0));
+
+    for _ in 0..rounds {
+        let move_a = a.next_move(rules, &history_a);
+        let move_b = b.next_move(rules, &history_b);
+
+        result_a.0 += rules.score(move_a, move_b);
+        result_b.0 += rules.score(move_b, move_a);
+        match rules.game.play(move_a, move_b) {
+            Outcome::Win => result_a.1 += 1,
+            Outcome::Lose => result_b.1 += 1,
+            Outcome::Draw => {}
+        }
+
+        history_a.push((move_a, move_b));
+        history_b.push((move_b, move_a));
+    }
+    (result_a, result_b)
+}
+
+/// Every strategy plays a match of `rounds` rounds against every other one.
+pub fn round_robin(
+    rules: &Rules,
+    strategies: &mut [Box<dyn Strategy>],
+    rounds: usize,
+) -> Standings {
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .map(|s| Standing {
+            name: s.name(),
+            ..Standing::default()
+        })
+        .collect();
+
+    for i in 0..strategies.len() {
+        for j in i + 1..strategies.len() {
+            let (left, right) = strategies.split_at_mut(j);
+            let (a, b) = play_match(rules, left[i].as_mut(), right[0].as_mut(), rounds);
+
+            standings[i].score += a.0;
+            standings[j].score += b.0;
+            match a.1.cmp(&b.1) {
+                std::cmp::Ordering::Greater => {
+                    standings[i].wins += 1;
+                    standings[j].losses += 1;
+                }
+                std::cmp::Ordering::Less => {
+                    standings[i].losses += 1;
+                    standings[j].wins += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    standings[i].draws += 1;
+                    standings[j].draws += 1;
+                }
+            }
+        }
+    }
+
+    // stable, so equal scores stay in the given order
+    standings.sort_by_key(|s| std::cmp::Reverse(s.score));
+    Standings(standings)
+}
+
+/// The built-in strategies, with `guide` playing the moves of a strategy
+/// guide.
+pub fn default_strategies(guide: FixedGuide, seed: u64) -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(guide),
+        Box::new(Random::new(seed)),
+        Box::new(FrequencyCounter::default()),
+        Box::new(PatternPredictor::new(2)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROCK: Move = Move(0);
+    const PAPER: Move = Move(1);
+    const SCISSORS: Move = Move(2);
+
+    #[test]
+    fn test_fixed_guide() {
+        let rules = Rules::default();
+        let mut guide = FixedGuide::from_guide(&rules, "A Y\nB X".lines()).unwrap();
+
+        let history = [(PAPER, ROCK)];
+        assert_eq!(guide.next_move(&rules, &[]), PAPER);
+        assert_eq!(guide.next_move(&rules, &history), ROCK);
+        assert_eq!(guide.next_move(&rules, &[history[0]; 2]), PAPER);
+    }
+
+    #[test]
+    fn test_random_is_seeded() {
+        let rules = Rules::default();
+        let moves = |strategy: &mut Random| -> Vec<Move> {
+            (0..20).map(|_| strategy.next_move(&rules, &[])).collect()
+        };
+
+        let mut random = Random::new(3);
+        let first = moves(&mut random);
+        random.reset();
+        assert_eq!(moves(&mut random), first);
+        assert_ne!(moves(&mut Random::new(4)), first);
+        assert!(first.iter().all(|m| m.0 < 3));
+    }
+
+    #[test]
+    fn test_frequency_counter() {
+        let rules = Rules::default();
+        let history = [(ROCK, SCISSORS), (ROCK, PAPER), (ROCK, PAPER)];
+
+        let mut counter = FrequencyCounter::default();
+
+        assert_eq!(counter.next_move(&rules, &[]), ROCK);
+        assert_eq!(counter.next_move(&rules, &history), SCISSORS);
+        // paper and scissors tie, paper comes first
+        assert_eq!(counter.next_move(&rules, &history[..2]), SCISSORS);
+    }
+
+    #[test]
+    fn test_history_changed_before_the_last_move() {
+        let rules = Rules::default();
+        let seen = [(ROCK, SCISSORS), (ROCK, SCISSORS)];
+        // the same second move, but a different first one
+        let history = [(ROCK, PAPER), (ROCK, SCISSORS), (ROCK, ROCK)];
+
+        let mut counter = FrequencyCounter::default();
+        let mut pattern = PatternPredictor::new(1);
+        counter.next_move(&rules, &seen);
+        pattern.next_move(&rules, &seen);
+
+        // one of each, the tie goes to rock
+        assert_eq!(counter.next_move(&rules, &history), PAPER);
+        assert_eq!(
+            pattern.next_move(&rules, &history),
+            PatternPredictor::new(1).next_move(&rules, &history)
+        );
+    }
+
+    #[test]
+    fn test_empty_guide() {
+        let rules = Rules::default();
+        assert!(matches!(
+            FixedGuide::from_guide(&rules, "\n\n".lines()),
+            Err(GuideError::Empty)
+        ));
+        assert!(matches!(
+            FixedGuide::from_guide(&rules, "A Q".lines()),
+            Err(GuideError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_counts_are_kept_between_rounds() {
+        let rules = Rules::default();
+        let mut random = Random::new(5);
+        let history: Vec<(Move, Move)> = (0..300)
+            .map(|_| (ROCK, random.next_move(&rules, &[])))
+            .collect();
+
+        let mut counter = FrequencyCounter::default();
+        let mut pattern = PatternPredictor::new(2);
+        for t in 0..=history.len() {
+            // a new strategy has to look at the whole history at once
+            assert_eq!(
+                counter.next_move(&rules, &history[..t]),
+                FrequencyCounter::default().next_move(&rules, &history[..t])
+            );
+            assert_eq!(
+                pattern.next_move(&rules, &history[..t]),
+                PatternPredictor::new(2).next_move(&rules, &history[..t])
+            );
+        }
+    }
+
+    #[test]
+    fn test_pattern_predictor_beats_cycles() {
+        let rules = Rules::default();
+        let mut cycle = FixedGuide::new(vec![ROCK, ROCK, PAPER, SCISSORS]);
+        let mut pattern = PatternPredictor::new(2);
+
+        let ((_, cycle_wins), (_, pattern_wins)) =
+            play_match(&rules, &mut cycle, &mut pattern, 100);
+        // it only needs one cycle to see every pattern
+        assert!(pattern_wins >= 95, "{} {}", cycle_wins, pattern_wins);
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let rules = Rules::default();
+        let guide = FixedGuide::new(vec![ROCK]);
+
+        let mut strategies = default_strategies(guide, 1);
+        let standings = round_robin(&rules, &mut strategies, 50);
+        assert_eq!(standings, round_robin(&rules, &mut strategies, 50));
+
+        let standings = standings.0;
+        assert_eq!(standings.len(), 4);
+        for s in &standings {
+            assert_eq!(s.wins + s.draws + s.losses, 3);
+        }
+        // always playing rock is beaten by both counting strategies
+        let guide = standings.iter().find(|s| s.name == "guide").unwrap();
+        assert!(guide.losses >= 2);
+        assert!(standings.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_standings_table() {
+        let standings = Standings(vec![Standing {
+            name: "guide".into(),
+            wins: 1,
+            draws: 2,
+            losses: 0,
+            score: 42,
+        }]);
+
+        assert_eq!(
+            standings.to_string(),
+            "strategy   won  draw  lost     score
+guide        1     2     0        42
+"
+        );
+    }
+}
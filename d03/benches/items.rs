@@ -0,0 +1,100 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use d03::{parse_lines, part_1, part_2};
+use itertools::Itertools;
+use std::collections::HashSet;
+
+static INPUT: &str = include_str!("../input.txt");
+
+/// The `HashSet` based solution the bitmask sets replaced, kept to compare
+/// against.
+mod hash_set {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+    pub struct Item(char);
+
+    impl Item {
+        fn priority(&self) -> u64 {
+            match self.0 {
+                'a'..='z' => self.0 as u64 - 'a' as u64 + 1,
+                'A'..='Z' => self.0 as u64 - 'A' as u64 + 27,
+                _ => unreachable!("unexpected character {}", self.0),
+            }
+        }
+    }
+
+    pub struct Rucksack {
+        left: HashSet<Item>,
+        right: HashSet<Item>,
+    }
+
+    impl Rucksack {
+        fn all_items(&self) -> HashSet<Item> {
+            self.left.iter().chain(self.right.iter()).cloned().collect()
+        }
+    }
+
+    pub fn parse_lines<T: AsRef<str>>(
+        lines: impl Iterator<Item = T>,
+    ) -> impl Iterator<Item = Rucksack> {
+        lines.filter_map(|line| {
+            let line = line.as_ref().trim();
+            if line.is_empty() {
+                return None;
+            }
+            let (lhs, rhs) = line.split_at(line.len() / 2);
+            Some(Rucksack {
+                left: lhs.chars().map(Item).collect(),
+                right: rhs.chars().map(Item).collect(),
+            })
+        })
+    }
+
+    pub fn part_1(rucksacks: impl Iterator<Item = Rucksack>) -> u64 {
+        rucksacks
+            .map(|r| {
+                let mut intersection = r.left.intersection(&r.right);
+                intersection.next().expect("no shared item").priority()
+            })
+            .sum()
+    }
+
+    pub fn part_2(rucksacks: impl Iterator<Item = Rucksack>) -> u64 {
+        rucksacks
+            .tuples::<(_, _, _)>()
+            .map(|(first, second, third)| {
+                let first = first.all_items();
+                let second = second.all_items();
+                let third = third.all_items();
+
+                let potentially_shared_items: HashSet<Item> =
+                    first.intersection(&second).cloned().collect();
+                let mut really_shared_items = potentially_shared_items.intersection(&third);
+                really_shared_items.next().unwrap().priority()
+            })
+            .sum()
+    }
+}
+
+fn items(c: &mut Criterion) {
+    let mut group = c.benchmark_group("d03 items");
+    group.throughput(Throughput::Bytes(INPUT.len() as u64));
+
+    group.bench_function("hash set part 1", |b| {
+        b.iter(|| hash_set::part_1(hash_set::parse_lines(black_box(INPUT).lines())))
+    });
+    group.bench_function("bitmask part 1", |b| {
+        b.iter(|| part_1(parse_lines(black_box(INPUT).lines())))
+    });
+    group.bench_function("hash set part 2", |b| {
+        b.iter(|| hash_set::part_2(hash_set::parse_lines(black_box(INPUT).lines())))
+    });
+    group.bench_function("bitmask part 2", |b| {
+        b.iter(|| part_2(parse_lines(black_box(INPUT).lines())))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, items);
+criterion_main!(benches);
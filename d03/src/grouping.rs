@@ -0,0 +1,345 @@
+use crate::{Item, ItemSet, Rucksack};
+
+/// A partition of rucksacks into groups sharing exactly one badge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grouping {
+    /// the lines of the rucksacks of every group, with the group's badge
+    pub groups: Vec<(Vec<usize>, Item)>,
+    /// whether this is the only such partition
+    pub unique: bool,
+}
+
+impl Grouping {
+    /// Sum of the priorities of the badges, as in part 2.
+    pub fn total(&self) -> u64 {
+        self.groups.iter().map(|(_, badge)| badge.priority()).sum()
+    }
+}
+
+/// Rucksacks by index, one bit each.
+#[derive(Debug, Clone)]
+struct Rucksacks(Vec<u64>);
+
+impl Rucksacks {
+    fn new(len: usize) -> Self {
+        Rucksacks(vec![0; len.div_ceil(64)])
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.0[i / 64] &= !(1 << (i % 64));
+    }
+}
+
+/// The indices of the set bits of `words`, which start at word `first`.
+fn indices(first: usize, words: impl Iterator<Item = u64>) -> impl Iterator<Item = usize> {
+    words.enumerate().flat_map(move |(w, mut bits)| {
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let bit = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(64 * (first + w) + bit)
+        })
+    })
+}
+
+struct Search<'a> {
+    items: Vec<ItemSet>,
+    /// `holders[p - 1]`: the rucksacks with the item of priority `p`
+    holders: Vec<Rucksacks>,
+    /// the rucksacks not in a group yet
+    free: Rucksacks,
+    group_size: usize,
+    groups: Vec<Vec<usize>>,
+    rucksacks: &'a [Rucksack],
+    found: Vec<Grouping>,
+}
+
+impl Search<'_> {
+    fn holders(&self, item: Item) -> &Rucksacks {
+        &self.holders[item.priority() as usize - 1]
+    }
+
+    /// The words of the free rucksacks from index `start` on which have
+    /// `badge` but none of the `other` items, starting at word `start / 64`.
+    fn members(&self, badge: Item, other: ItemSet, start: usize) -> impl Iterator<Item = u64> + '_ {
+        let first = start / 64;
+        (first..self.free.0.len()).map(move |w| {
+            let mut bits = self.free.0[w] & self.holders(badge).0[w];
+            for item in other.iter() {
+                bits &= !self.holders(item).0[w];
+            }
+            if w == first {
+                bits &= !0 << (start % 64);
+            }
+            bits
+        })
+    }
+
+    /// The free rucksacks from index `start` on which can join a group
+    /// sharing `shared` around `badge`. The last one must leave `badge` as
+    /// the only shared item, the others only need to have it.
+    fn candidates(
+        &self,
+        badge: Item,
+        shared: ItemSet,
+        start: usize,
+        last: bool,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let mut other = ItemSet::new();
+        if last {
+            other = shared;
+            other.remove(badge);
+        }
+        indices(start / 64, self.members(badge, other, start))
+    }
+
+    /// How many groups the free rucksack `i` can still be in, counting no
+    /// further than `limit`.
+    fn options(&mut self, i: usize, limit: usize) -> usize {
+        self.free.remove(i);
+        let mut count = 0;
+        for badge in self.items[i].iter() {
+            self.count(
+                badge,
+                0,
+                self.items[i],
+                self.group_size - 1,
+                limit,
+                &mut count,
+            );
+            if count >= limit {
+                break;
+            }
+        }
+        self.free.insert(i);
+        count
+    }
+
+    /// Count the ways to add `missing` rucksacks to a group as
+    /// [`Self::extend`] does.
+    fn count(
+        &self,
+        badge: Item,
+        start: usize,
+        shared: ItemSet,
+        missing: usize,
+        limit: usize,
+        count: &mut usize,
+    ) {
+        match missing {
+            0 => *count += usize::from(shared.len() == 1),
+            1 => *count += self.candidates(badge, shared, start, true).count(),
+            _ => {
+                for j in self.candidates(badge, shared, start, false) {
+                    if *count >= limit {
+                        return;
+                    }
+                    let shared = shared & self.items[j];
+                    self.count(badge, j + 1, shared, missing - 1, limit, count);
+                }
+            }
+        }
+    }
+
+    fn solve(&mut self) {
+        if self.found.len() > 1 {
+            return;
+        }
+
+        // the rucksack with the fewest options, none means a dead end
+        let free: Vec<usize> = indices(0, self.free.0.iter().copied()).collect();
+        let mut next: Option<(usize, usize)> = None;
+        for i in free {
+            let limit = next.map_or(usize::MAX, |(fewest, _)| fewest);
+            let options = self.options(i, limit);
+            if options == 0 {
+                return;
+            }
+            if options < limit {
+                next = Some((options, i));
+            }
+        }
+        let Some((_, first)) = next else {
+            self.record();
+            return;
+        };
+
+        self.free.remove(first);
+        self.groups.push(vec![first]);
+        for badge in self.items[first].iter() {
+            self.extend(badge, 0, self.items[first], self.group_size - 1);
+        }
+        self.groups.pop();
+        self.free.insert(first);
+    }
+
+    /// Add `missing` more rucksacks to the last group, which shares `shared`
+    /// so far, so that it only shares `badge`. The rucksacks are added by
+    /// index from `start` on, so every group is only tried once.
+    fn extend(&mut self, badge: Item, start: usize, shared: ItemSet, missing: usize) {
+        if missing == 0 {
+            if shared.len() == 1 {
+                self.solve();
+            }
+            return;
+        }
+
+        let candidates: Vec<usize> = self
+            .candidates(badge, shared, start, missing == 1)
+            .collect();
+        for j in candidates {
+            if self.found.len() > 1 {
+                return;
+            }
+
+            self.free.remove(j);
+            self.groups.last_mut().expect("a group").push(j);
+            self.extend(badge, j + 1, shared & self.items[j], missing - 1);
+            self.groups.last_mut().expect("a group").pop();
+            self.free.insert(j);
+        }
+    }
+
+    fn record(&mut self) {
+        let groups = self
+            .groups
+            .iter()
+            .map(|group| {
+                let badge = group
+                    .iter()
+                    .map(|&i| self.items[i])
+                    .reduce(|a, b| a & b)
+                    .and_then(|items| items.single())
+                    .expect("groups share one badge");
+                let mut lines: Vec<usize> = group.iter().map(|&i| self.rucksacks[i].line).collect();
+                lines.sort_unstable();
+                (lines, badge)
+            })
+            .collect();
+        self.found.push(Grouping {
+            groups,
+            unique: true,
+        });
+    }
+}
+
+/// Partition rucksacks in any order into groups of `group_size` rucksacks
+/// sharing exactly one item each, or `None` if there is no such partition.
+///
+/// This is a backtracking search. Every group is built around its badge,
+/// and the rucksack with the fewest possible groups left is grouped next.
+/// The rucksacks with every item are kept as bitmasks, so the rucksacks
+/// completing a group are found a word at a time, and a branch is given up
+/// as soon as some rucksack can't be in any group anymore. The search stops
+/// at the second partition it finds, which only tells that the first one
+/// isn't unique.
+pub fn find_groups(rucksacks: &[Rucksack], group_size: usize) -> Option<Grouping> {
+    assert!(group_size > 0, "a group needs a rucksack");
+    if !rucksacks.len().is_multiple_of(group_size) {
+        return None;
+    }
+
+    let items: Vec<ItemSet> = rucksacks.iter().map(|r| r.all_items()).collect();
+    let mut holders = vec![Rucksacks::new(items.len()); 52];
+    let mut free = Rucksacks::new(items.len());
+    for (i, set) in items.iter().enumerate() {
+        free.insert(i);
+        for item in set.iter() {
+            holders[item.priority() as usize - 1].insert(i);
+        }
+    }
+
+    let mut search = Search {
+        items,
+        holders,
+        free,
+        group_size,
+        groups: Vec::new(),
+        rucksacks,
+        found: Vec::new(),
+    };
+    search.solve();
+
+    let unique = search.found.len() == 1;
+    search
+        .found
+        .into_iter()
+        .next()
+        .map(|grouping| Grouping { unique, ..grouping })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_lines, part_2};
+
+    fn rucksacks(input: &str) -> Vec<Rucksack> {
+        parse_lines(input.lines())
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    static SHUFFLED: &str = "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+vJrwpWtwJgWrhcsFMMfFFhFp
+CrZsJsPPZsGzwwsLwLmpwMDw
+PmmdzqPrVvPwwTWBwg
+ttgJtRGJQctTZtZT
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL";
+
+    #[test]
+    fn test_shuffled_example() {
+        let grouping = find_groups(&rucksacks(SHUFFLED), 3).unwrap();
+
+        // not necessarily the groups of the example, but valid ones
+        let mut lines: Vec<usize> = grouping
+            .groups
+            .iter()
+            .flat_map(|(g, _)| g.clone())
+            .collect();
+        let ordered: Vec<&str> = lines
+            .iter()
+            .map(|&line| SHUFFLED.lines().nth(line - 1).unwrap())
+            .collect();
+        assert_eq!(
+            part_2(parse_lines(ordered.into_iter())),
+            Ok(grouping.total())
+        );
+
+        lines.sort_unstable();
+        assert_eq!(lines, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_unique() {
+        // the rucksacks with `a` share nothing with the ones with `b`
+        let grouping = find_groups(&rucksacks("axxa\nayya\nazza\nbppb\nbqqb\nbrrb"), 3).unwrap();
+        assert!(grouping.unique);
+        assert_eq!(
+            grouping.groups,
+            vec![(vec![1, 2, 3], Item('a')), (vec![4, 5, 6], Item('b'))]
+        );
+    }
+
+    #[test]
+    fn test_not_unique() {
+        // every pair shares `a`, so any split works
+        let grouping = find_groups(&rucksacks("abba\nacca\nadda\naeea"), 2).unwrap();
+        assert!(!grouping.unique);
+        assert_eq!(grouping.total(), 2);
+    }
+
+    #[test]
+    fn test_impossible() {
+        // `c` shares nothing with anyone
+        assert_eq!(find_groups(&rucksacks("axxa\nayya\nczzc"), 3), None);
+        // every group shares both `a` and `b`
+        assert_eq!(find_groups(&rucksacks("abab\nbaba\nabba"), 3), None);
+        assert_eq!(find_groups(&rucksacks("axxa\nayya"), 3), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fmt,
+    ops::{BitAnd, BitOr},
+};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Item(pub(crate) char);
+
+impl Item {
+    pub(crate) fn priority(&self) -> u64 {
+        match self.0 {
+            'a'..='z' => self.0 as u64 - 'a' as u64 + 1,
+            'A'..='Z' => self.0 as u64 - 'A' as u64 + 27,
+            _ => unreachable!("unexpected character {}", self.0),
+        }
+    }
+
+    /// The item with the given priority, `1..=52`.
+    fn from_priority(priority: u32) -> Item {
+        match priority {
+            1..=26 => Item((b'a' + priority as u8 - 1) as char),
+            27..=52 => Item((b'A' + priority as u8 - 27) as char),
+            _ => unreachable!("unexpected priority {}", priority),
+        }
+    }
+}
+
+/// A set of items, one bit per item: bit `p - 1` for the item with
+/// priority `p`.
+#[derive(Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn new() -> Self {
+        ItemSet(0)
+    }
+
+    pub fn insert(&mut self, item: Item) {
+        self.0 |= 1 << (item.priority() - 1);
+    }
+
+    pub fn remove(&mut self, item: Item) {
+        self.0 &= !(1 << (item.priority() - 1));
+    }
+
+    pub fn contains(&self, item: Item) -> bool {
+        self.0 & (1 << (item.priority() - 1)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The only item of the set, `None` if it has none or several.
+    pub fn single(&self) -> Option<Item> {
+        if self.len() == 1 {
+            self.iter().next()
+        } else {
+            None
+        }
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    /// The items by priority, lowest first.
+    pub fn iter(&self) -> impl Iterator<Item = Item> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let bit = bits.trailing_zeros();
+            bits &= bits - 1;
+            Some(Item::from_priority(bit + 1))
+        })
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: ItemSet) -> ItemSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        self.intersection(other)
+    }
+}
+
+impl FromIterator<Item> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = Item>>(items: I) -> Self {
+        let mut set = ItemSet::new();
+        for item in items {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+impl fmt::Debug for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|item| item.0))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn set(items: &str) -> ItemSet {
+        items.chars().map(Item).collect()
+    }
+
+    #[test_case('a', 1 ; "lower case a")]
+    #[test_case('z', 26 ; "lower case z")]
+    #[test_case('A', 27 ; "upper case A")]
+    #[test_case('Z', 52 ; "upper case Z")]
+    fn test_priority(ch: char, prio: u64) {
+        assert_eq!(Item(ch).priority(), prio);
+        assert_eq!(Item::from_priority(prio as u32), Item(ch));
+    }
+
+    #[test]
+    fn test_set() {
+        let a = set("abcZ");
+        let b = set("cdZZ");
+
+        assert_eq!(a.len(), 4);
+        assert!(a.contains(Item('Z')));
+        assert!(!a.contains(Item('d')));
+        assert_eq!(a & b, set("cZ"));
+        assert_eq!(a | b, set("abcdZ"));
+        assert!((set("ab") & set("AB")).is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let items: String = set("ZzAaM").iter().map(|item| item.0).collect();
+        assert_eq!(items, "azAMZ");
+        assert_eq!(ItemSet::new().iter().count(), 0);
+        assert_eq!(format!("{:?}", set("ba")), "{'a', 'b'}");
+    }
+}
@@ -0,0 +1,256 @@
+mod grouping;
+mod items;
+mod validate;
+pub use grouping::{find_groups, Grouping};
+pub use items::{Item, ItemSet};
+pub use validate::{validate, Problem, Violation};
+
+#[derive(Debug)]
+pub struct Rucksack {
+    /// the line of the input
+    line: usize,
+    /// everything in the rucksack
+    items: ItemSet,
+    /// the items in every compartment
+    shared: ItemSet,
+}
+
+impl Rucksack {
+    fn all_items(&self) -> ItemSet {
+        self.items
+    }
+}
+
+/// The rucksack on the line with number `number`, `None` for blank lines,
+/// or everything wrong with the line.
+pub(crate) fn parse_rucksack(
+    raw: &str,
+    number: usize,
+    compartments: usize,
+) -> Result<Option<Rucksack>, Vec<Violation>> {
+    assert!(compartments > 0, "a rucksack needs a compartment");
+
+    let line = raw.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    if !line.bytes().all(|b| b.is_ascii_alphabetic()) || !line.len().is_multiple_of(compartments) {
+        return Err(problems(raw, number, compartments));
+    }
+
+    let size = line.len() / compartments;
+    let mut compartments = line
+        .as_bytes()
+        .chunks(size)
+        .map(|compartment| compartment.iter().map(|&b| Item(b as char)).collect());
+    let first: ItemSet = compartments.next().expect("line is not empty");
+
+    let (items, shared) = compartments.fold((first, first), |(items, shared), compartment| {
+        (items | compartment, shared & compartment)
+    });
+    Ok(Some(Rucksack {
+        line: number,
+        items,
+        shared,
+    }))
+}
+
+#[cold]
+fn problems(raw: &str, number: usize, compartments: usize) -> Vec<Violation> {
+    let line = raw.trim();
+    let indent = raw[..raw.len() - raw.trim_start().len()].chars().count();
+    let violation = |problem| Violation {
+        line: number,
+        problem,
+    };
+
+    let mut violations: Vec<Violation> = line
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_ascii_alphabetic())
+        .map(|(i, found)| {
+            violation(Problem::InvalidItem {
+                column: indent + i + 1,
+                found,
+            })
+        })
+        .collect();
+
+    let length = line.chars().count();
+    if !length.is_multiple_of(compartments) {
+        violations.push(violation(Problem::UnevenLength {
+            length,
+            compartments,
+        }));
+    }
+    violations
+}
+
+/// Rucksacks with two compartments, as in the puzzle.
+pub fn parse_lines<T: AsRef<str>>(
+    lines: impl Iterator<Item = T>,
+) -> impl Iterator<Item = Result<Rucksack, Violation>> {
+    parse_compartments(lines, 2)
+}
+
+/// Rucksacks with `compartments` compartments of the same size, or the
+/// first thing wrong with a line.
+pub fn parse_compartments<T: AsRef<str>>(
+    lines: impl Iterator<Item = T>,
+    compartments: usize,
+) -> impl Iterator<Item = Result<Rucksack, Violation>> {
+    assert!(compartments > 0, "a rucksack needs a compartment");
+
+    lines.enumerate().filter_map(move |(i, line)| {
+        parse_rucksack(line.as_ref(), i + 1, compartments)
+            .map_err(|violations| violations.into_iter().next().expect("a violation"))
+            .transpose()
+    })
+}
+
+pub fn part_1(
+    rucksacks: impl Iterator<Item = Result<Rucksack, Violation>>,
+) -> Result<u64, Violation> {
+    let mut total = 0;
+    for r in rucksacks {
+        let r = r?;
+        let shared_item = r.shared.single().ok_or(Violation {
+            line: r.line,
+            problem: Problem::SharedItems(r.shared),
+        })?;
+        total += shared_item.priority();
+    }
+    Ok(total)
+}
+
+pub fn part_2(
+    rucksacks: impl Iterator<Item = Result<Rucksack, Violation>>,
+) -> Result<u64, Violation> {
+    badges(rucksacks, 3)
+}
+
+/// Sum of the priorities of the items shared by every rucksack of a group,
+/// for consecutive groups of `group_size` rucksacks.
+pub fn badges(
+    rucksacks: impl Iterator<Item = Result<Rucksack, Violation>>,
+    group_size: usize,
+) -> Result<u64, Violation> {
+    assert!(group_size > 0, "a group needs a rucksack");
+
+    let mut total = 0;
+    let mut lines = Vec::with_capacity(group_size);
+    let mut shared = ItemSet::new();
+
+    for r in rucksacks {
+        let r = r?;
+        shared = if lines.is_empty() {
+            r.all_items()
+        } else {
+            shared & r.all_items()
+        };
+        lines.push(r.line);
+
+        if lines.len() == group_size {
+            let badge = shared.single().ok_or_else(|| Violation {
+                line: lines[0],
+                problem: Problem::Badges {
+                    lines: lines.clone(),
+                    items: shared,
+                },
+            })?;
+            total += badge.priority();
+            lines.clear();
+        }
+    }
+
+    if let Some(&line) = lines.first() {
+        return Err(Violation {
+            line,
+            problem: Problem::IncompleteGroup {
+                rucksacks: lines.len(),
+                group_size,
+            },
+        });
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    static TEST_INPUT: &str = "
+        vJrwpWtwJgWrhcsFMMfFFhFp
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+        PmmdzqPrVvPwwTWBwg
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+        ttgJtRGJQctTZtZT
+        CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn test_1() {
+        assert_eq!(part_1(parse_lines(TEST_INPUT.lines())), Ok(157))
+    }
+
+    #[test]
+    fn test_2() {
+        assert_eq!(part_2(parse_lines(TEST_INPUT.lines())), Ok(70))
+    }
+
+    #[test]
+    fn test_compartments() {
+        // `b` is in all three thirds of the first rucksack, `x` of the second
+        let input = "abcbxbadb\nxyzxwvxut";
+        assert_eq!(part_1(parse_compartments(input.lines(), 3)), Ok(2 + 24));
+    }
+
+    #[test_case(2, 1 + 1; "pairs")]
+    #[test_case(4, 1; "everyone")]
+    fn test_group_sizes(group_size: usize, expected: u64) {
+        let input = "abcd\naefg\nahij\naklm";
+        assert_eq!(badges(parse_lines(input.lines()), group_size), Ok(expected));
+    }
+
+    #[test]
+    fn test_uneven_groups() {
+        // the first line is empty
+        let input = TEST_INPUT.lines().take(6);
+        assert_eq!(
+            part_2(parse_lines(input)),
+            Err(Violation {
+                line: 5,
+                problem: Problem::IncompleteGroup {
+                    rucksacks: 2,
+                    group_size: 3
+                }
+            })
+        );
+    }
+
+    #[test_case("abca\nab-a", 2, Problem::InvalidItem { column: 3, found: '-' }; "invalid item")]
+    #[test_case("abca\nabc", 2, Problem::UnevenLength { length: 3, compartments: 2 }; "odd length")]
+    #[test_case("abca\nabab", 2, Problem::SharedItems("ab".chars().map(Item).collect()); "several shared items")]
+    #[test_case("abca\nabcd", 2, Problem::SharedItems(ItemSet::new()); "no shared item")]
+    fn test_part_1_errors(input: &str, line: usize, problem: Problem) {
+        assert_eq!(
+            part_1(parse_lines(input.lines())),
+            Err(Violation { line, problem })
+        );
+    }
+
+    #[test]
+    fn test_no_badge() {
+        let input = "abca\nadaa\nAbcA\nxyzx";
+        assert_eq!(
+            badges(parse_lines(input.lines()), 2),
+            Err(Violation {
+                line: 3,
+                problem: Problem::Badges {
+                    lines: vec![3, 4],
+                    items: ItemSet::new()
+                }
+            })
+        );
+    }
+}
@@ -1,123 +1,83 @@
-use itertools::Itertools;
-use std::collections::HashSet;
+use d03::{badges, find_groups, parse_compartments, part_1, validate};
+use std::fmt::Display;
 
-fn main() {
-    let lines: Vec<String> = std::fs::read_to_string(std::env::args().nth(1).unwrap())
-        .unwrap()
-        .lines()
-        .map(|l| l.to_string())
-        .collect();
-
-    println!("day 1: {}", part_1(parse_lines(lines.iter())));
-    println!("day 2: {}", part_2(parse_lines(lines.iter())));
-}
-
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Item(char);
+const USAGE: &str =
+    "usage: d03 <input> [--compartments <n>] [--group <n>] [--validate | --shuffled]";
 
-impl Item {
-    fn priority(&self) -> u64 {
-        match self.0 {
-            'a'..='z' => self.0 as u64 - 'a' as u64 + 1,
-            'A'..='Z' => self.0 as u64 - 'A' as u64 + 27,
-            _ => unreachable!("unexpected character {}", self.0),
+fn main() {
+    let input = shared::input::load();
+
+    let mut compartments = 2;
+    let mut group_size = 3;
+    let mut validating = false;
+    let mut shuffled = false;
+
+    let mut args = std::env::args().skip(2);
+    while let Some(arg) = args.next() {
+        let mut number = || -> usize {
+            let n = args
+                .next()
+                .unwrap_or_else(|| usage(&format!("missing number after {}", arg)));
+            n.parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .unwrap_or_else(|| usage(&format!("invalid number {:?} after {}", n, arg)))
+        };
+        match arg.as_str() {
+            "--compartments" => compartments = number(),
+            "--group" => group_size = number(),
+            "--validate" => validating = true,
+            "--shuffled" => shuffled = true,
+            _ => usage(&format!("unknown argument {:?}", arg)),
         }
     }
-}
-
-#[derive(Debug)]
-struct Rucksack {
-    left: HashSet<Item>,
-    right: HashSet<Item>,
-}
 
-impl Rucksack {
-    fn all_items(&self) -> HashSet<Item> {
-        self.left.iter().chain(self.right.iter()).cloned().collect()
+    if validating {
+        let violations = validate(input.lines(), compartments, group_size);
+        for violation in &violations {
+            println!("{}", violation);
+        }
+        if !violations.is_empty() {
+            fail(format!("{} violations", violations.len()));
+        }
+        return;
     }
-}
 
-fn parse_lines<T: AsRef<str>>(lines: impl Iterator<Item = T>) -> impl Iterator<Item = Rucksack> {
-    lines.filter_map(|line| {
-        let line = line.as_ref().trim();
-        if line.is_empty() {
-            return None;
+    let rucksacks = || parse_compartments(input.lines(), compartments);
+
+    if shuffled {
+        // the groups are found again instead of read from consecutive lines
+        let rucksacks: Vec<_> = rucksacks()
+            .collect::<Result<_, _>>()
+            .unwrap_or_else(|err| fail(err));
+        let grouping = find_groups(&rucksacks, group_size)
+            .unwrap_or_else(|| fail("the rucksacks can't be grouped"));
+        for (lines, badge) in &grouping.groups {
+            println!("{:?}: {:?}", lines, badge);
         }
+        println!("day 2: {}", grouping.total());
+        if !grouping.unique {
+            println!("(not the only grouping)");
+        }
+        return;
+    }
 
-        debug_assert!(line.len() % 2 == 0);
-
-        let (lhs, rhs) = line.split_at(line.len() / 2);
-
-        debug_assert!(lhs.len() == rhs.len());
-
-        Some(Rucksack {
-            left: lhs.chars().map(Item).collect(),
-            right: rhs.chars().map(Item).collect(),
-        })
-    })
-}
-
-fn part_1(rucksacks: impl Iterator<Item = Rucksack>) -> u64 {
-    rucksacks
-        .map(|r| {
-            let mut intersection = r.left.intersection(&r.right);
-            let shared_item = intersection.next().expect("no shared item");
-
-            debug_assert!(intersection.next().is_none());
-
-            shared_item.priority()
-        })
-        .sum::<u64>()
+    println!(
+        "day 1: {}",
+        part_1(rucksacks()).unwrap_or_else(|err| fail(err))
+    );
+    println!(
+        "day 2: {}",
+        badges(rucksacks(), group_size).unwrap_or_else(|err| fail(err))
+    );
 }
 
-fn part_2(rucksacks: impl Iterator<Item = Rucksack>) -> u64 {
-    rucksacks
-        .tuples::<(_, _, _)>()
-        .map(|(first, second, third)| {
-            let first = first.all_items();
-            let second = second.all_items();
-            let third = third.all_items();
-
-            let potentially_shared_items: HashSet<Item> =
-                first.intersection(&second).cloned().collect();
-            let mut really_shared_items = potentially_shared_items.intersection(&third);
-
-            let result = really_shared_items.next().unwrap();
-            debug_assert!(really_shared_items.next().is_none());
-
-            result.priority()
-        })
-        .sum::<u64>()
+fn usage(problem: &str) -> ! {
+    eprintln!("{}\n{}", problem, USAGE);
+    std::process::exit(2);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use test_case::test_case;
-
-    static TEST_INPUT: &str = "
-        vJrwpWtwJgWrhcsFMMfFFhFp
-        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-        PmmdzqPrVvPwwTWBwg
-        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-        ttgJtRGJQctTZtZT
-        CrZsJsPPZsGzwwsLwLmpwMDw";
-
-    #[test]
-    fn test_1() {
-        assert_eq!(part_1(parse_lines(TEST_INPUT.lines())), 157)
-    }
-
-    #[test]
-    fn test_2() {
-        assert_eq!(part_2(parse_lines(TEST_INPUT.lines())), 70)
-    }
-
-    #[test_case('a', 1 ; "lower case a")]
-    #[test_case('z', 26 ; "lower case z")]
-    #[test_case('A', 27 ; "upper case A")]
-    #[test_case('Z', 52 ; "upper case Z")]
-    fn test_priority(ch: char, prio: u64) {
-        assert_eq!(Item(ch).priority(), prio);
-    }
+fn fail(err: impl Display) -> ! {
+    eprintln!("{}", err);
+    std::process::exit(1);
 }
@@ -0,0 +1,237 @@
+use crate::{parse_rucksack, ItemSet};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// the line doesn't split into compartments of the same size
+    UnevenLength { length: usize, compartments: usize },
+    /// not an item, `column` is 1-based and counts characters
+    InvalidItem { column: usize, found: char },
+    /// the compartments share no item, or more than one
+    SharedItems(ItemSet),
+    /// the rucksacks of a group share no item, or more than one
+    Badges { lines: Vec<usize>, items: ItemSet },
+    /// the last group misses rucksacks
+    IncompleteGroup { rucksacks: usize, group_size: usize },
+}
+
+/// Something wrong with the rucksack, or the group starting, on `line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+fn describe(items: ItemSet) -> String {
+    if items.is_empty() {
+        return "no item".into();
+    }
+    items
+        .iter()
+        .map(|item| item.0.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.problem {
+            Problem::UnevenLength {
+                length,
+                compartments,
+            } => write!(
+                f,
+                "{} items don't split into {} compartments",
+                length, compartments
+            ),
+            Problem::InvalidItem { column, found } => {
+                write!(f, "{:?} at column {} is not an item", found, column)
+            }
+            Problem::SharedItems(items) => {
+                write!(f, "the compartments share {}", describe(*items))
+            }
+            Problem::Badges { lines, items } => {
+                let lines: Vec<String> = lines.iter().map(usize::to_string).collect();
+                write!(
+                    f,
+                    "the group of lines {} shares {}",
+                    lines.join(", "),
+                    describe(*items)
+                )
+            }
+            Problem::IncompleteGroup {
+                rucksacks,
+                group_size,
+            } => write!(
+                f,
+                "the last group has {} rucksacks instead of {}",
+                rucksacks, group_size
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// Every violation in the rucksacks of the lines, in line order, instead of
+/// only the first one as when solving.
+///
+/// Lines that aren't valid rucksacks still take their place in a group, but
+/// the items of their group aren't checked.
+pub fn validate<T: AsRef<str>>(
+    lines: impl Iterator<Item = T>,
+    compartments: usize,
+    group_size: usize,
+) -> Vec<Violation> {
+    assert!(group_size > 0, "a group needs a rucksack");
+
+    let mut violations = Vec::new();
+    // the line and items of every rucksack of the current group
+    let mut group: Vec<(usize, Option<ItemSet>)> = Vec::with_capacity(group_size);
+
+    for (i, line) in lines.enumerate() {
+        let number = i + 1;
+        match parse_rucksack(line.as_ref(), number, compartments) {
+            Ok(None) => continue,
+            Ok(Some(rucksack)) => {
+                if rucksack.shared.len() != 1 {
+                    violations.push(Violation {
+                        line: number,
+                        problem: Problem::SharedItems(rucksack.shared),
+                    });
+                }
+                group.push((number, Some(rucksack.items)));
+            }
+            Err(problems) => {
+                violations.extend(problems);
+                group.push((number, None));
+            }
+        }
+
+        if group.len() == group_size {
+            let items = group
+                .iter()
+                .map(|&(_, items)| items)
+                .reduce(|a, b| Some(a? & b?))
+                .flatten();
+            if let Some(items) = items.filter(|items| items.len() != 1) {
+                violations.push(Violation {
+                    line: group[0].0,
+                    problem: Problem::Badges {
+                        lines: group.iter().map(|&(line, _)| line).collect(),
+                        items,
+                    },
+                });
+            }
+            group.clear();
+        }
+    }
+
+    if let Some(&(line, _)) = group.first() {
+        violations.push(Violation {
+            line,
+            problem: Problem::IncompleteGroup {
+                rucksacks: group.len(),
+                group_size,
+            },
+        });
+    }
+
+    // groups are checked after the problems of their later lines
+    violations.sort_by_key(|violation| violation.line);
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Item;
+
+    fn set(items: &str) -> ItemSet {
+        items.chars().map(Item).collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg";
+        assert_eq!(validate(input.lines(), 2, 3), vec![]);
+    }
+
+    #[test]
+    fn test_violations() {
+        let input = "abca
+
+ab
+abcd
+aBc
+a-x!
+abab
+aa";
+        let violation = |line, problem| Violation { line, problem };
+
+        assert_eq!(
+            validate(input.lines(), 2, 3),
+            vec![
+                violation(
+                    1,
+                    Problem::Badges {
+                        lines: vec![1, 3, 4],
+                        items: set("ab")
+                    }
+                ),
+                violation(3, Problem::SharedItems(ItemSet::new())),
+                violation(4, Problem::SharedItems(ItemSet::new())),
+                violation(
+                    5,
+                    Problem::UnevenLength {
+                        length: 3,
+                        compartments: 2
+                    }
+                ),
+                violation(
+                    6,
+                    Problem::InvalidItem {
+                        column: 2,
+                        found: '-'
+                    }
+                ),
+                violation(
+                    6,
+                    Problem::InvalidItem {
+                        column: 4,
+                        found: '!'
+                    }
+                ),
+                violation(7, Problem::SharedItems(set("ab"))),
+                violation(
+                    8,
+                    Problem::IncompleteGroup {
+                        rucksacks: 1,
+                        group_size: 3
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "ab\n  aä\nab";
+        let messages: Vec<String> = validate(input.lines(), 2, 3)
+            .iter()
+            .map(Violation::to_string)
+            .collect();
+
+        assert_eq!(
+            messages,
+            [
+                "line 1: the compartments share no item",
+                "line 2: 'ä' at column 4 is not an item",
+                "line 3: the compartments share no item",
+            ]
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use d05::{parse_lines, part_1, part_2};
+
+static INPUT: &str = include_str!("../input.txt");
+
+fn lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("d05");
+
+    group.bench_function("owned lines", |b| {
+        b.iter(|| {
+            let lines: Vec<String> = black_box(INPUT).lines().map(|l| l.to_string()).collect();
+            let (stacks, moves) = parse_lines(lines.iter());
+            (part_1(stacks.clone(), moves.clone()), part_2(stacks, moves))
+        })
+    });
+
+    group.bench_function("borrowed lines", |b| {
+        b.iter(|| {
+            let (stacks, moves) = parse_lines(black_box(INPUT).lines());
+            (part_1(stacks.clone(), moves.clone()), part_2(stacks, moves))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, lines);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+use itertools::Itertools;
+
+fn find_marker(input: &str, marker_length: usize) -> usize {
+    // the input is plain ASCII, so we can look at the bytes without
+    // collecting the chars of a potentially huge input first.
+    for (i, window) in input.as_bytes().windows(marker_length).enumerate() {
+        if window.iter().duplicates().next().is_none() {
+            return i + marker_length;
+        }
+    }
+    unreachable!();
+}
+
+pub fn part_1(input: &str) -> usize {
+    find_marker(input, 4)
+}
+
+pub fn part_2(input: &str) -> usize {
+    find_marker(input, 14)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7)]
+    #[test_case("bvwbjplbgvbhsrlpgdmjqwftvncz", 5)]
+    #[test_case("nppdvjthqldpwncqszvftbrmjlhg", 6)]
+    #[test_case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10)]
+    #[test_case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11)]
+    fn test_1(input: &str, expected: usize) {
+        assert_eq!(part_1(input), expected);
+    }
+
+    #[test_case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19)]
+    #[test_case("bvwbjplbgvbhsrlpgdmjqwftvncz", 23)]
+    #[test_case("nppdvjthqldpwncqszvftbrmjlhg", 23)]
+    #[test_case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29)]
+    #[test_case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26)]
+    fn test_2(input: &str, expected: usize) {
+        assert_eq!(part_2(input), expected);
+    }
+}
@@ -1,50 +1,9 @@
-use itertools::Itertools;
+use d06::{part_1, part_2};
 
 fn main() {
-    let input = std::fs::read_to_string(std::env::args().nth(1).unwrap()).unwrap();
+    let input = shared::input::map();
+    let input = input.as_str().expect("input is not utf-8");
 
-    println!("day 1: {}", part_1(&input));
-    println!("day 2: {}", part_2(&input));
-}
-
-fn find_marker(input: &str, marker_length: usize) -> usize {
-    let chars: Vec<_> = input.chars().collect();
-    for (i, window) in chars.windows(marker_length).enumerate() {
-        if window.iter().duplicates().next().is_none() {
-            return i + marker_length;
-        }
-    }
-    unreachable!();
-}
-
-fn part_1(input: &str) -> usize {
-    find_marker(input, 4)
-}
-
-fn part_2(input: &str) -> usize {
-    find_marker(input, 14)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use test_case::test_case;
-
-    #[test_case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7)]
-    #[test_case("bvwbjplbgvbhsrlpgdmjqwftvncz", 5)]
-    #[test_case("nppdvjthqldpwncqszvftbrmjlhg", 6)]
-    #[test_case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10)]
-    #[test_case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11)]
-    fn test_1(input: &str, expected: usize) {
-        assert_eq!(part_1(input), expected);
-    }
-
-    #[test_case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19)]
-    #[test_case("bvwbjplbgvbhsrlpgdmjqwftvncz", 23)]
-    #[test_case("nppdvjthqldpwncqszvftbrmjlhg", 23)]
-    #[test_case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29)]
-    #[test_case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26)]
-    fn test_2(input: &str, expected: usize) {
-        assert_eq!(part_2(input), expected);
-    }
+    println!("day 1: {}", part_1(input));
+    println!("day 2: {}", part_2(input));
 }
@@ -0,0 +1,56 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    // builds don't touch the source tree, a test compares this with the
+    // committed include/aoc.h
+    let include_dir = out_dir.join("include");
+    std::fs::create_dir_all(&include_dir).expect("could not create the include directory");
+    cbindgen::generate_with_config(
+        &crate_dir,
+        cbindgen::Config::from_root_or_default(&crate_dir),
+    )
+    .expect("could not generate C header")
+    .write_to_file(include_dir.join("aoc.h"));
+    println!(
+        "cargo:rustc-env=AOC_FFI_INCLUDE_DIR={}",
+        include_dir.display()
+    );
+
+    match native_static_libs(&out_dir) {
+        Some(libs) => println!("cargo:rustc-env=AOC_FFI_NATIVE_LIBS={}", libs),
+        None => println!("cargo:warning=could not find the native libraries of a static library"),
+    }
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
+
+/// The system libraries that C programs linking a static Rust library need
+/// on the target, as rustc reports them for an empty one.
+fn native_static_libs(out_dir: &Path) -> Option<String> {
+    let source = out_dir.join("empty.rs");
+    std::fs::write(&source, "").ok()?;
+
+    let output = Command::new(env::var("RUSTC").ok()?)
+        .args(["--crate-type", "staticlib", "--crate-name", "empty"])
+        .args(["--print", "native-static-libs"])
+        .arg("--target")
+        .arg(env::var("TARGET").ok()?)
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg(&source)
+        .output()
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .find_map(|line| line.split_once("native-static-libs: "))
+        .map(|(_, libs)| libs.trim().to_string())
+}
@@ -0,0 +1,68 @@
+#ifndef AOC_H
+#define AOC_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The answer was written to the output buffer.
+ */
+#define AOC_OK 0
+
+/**
+ * The input or output pointer was null.
+ */
+#define AOC_ERR_NULL_POINTER -1
+
+/**
+ * The input is not valid UTF-8.
+ */
+#define AOC_ERR_INVALID_UTF8 -2
+
+/**
+ * There is no solver for this day and part.
+ */
+#define AOC_ERR_UNKNOWN_PUZZLE -3
+
+/**
+ * The answer plus its terminating NUL doesn't fit into the output buffer.
+ */
+#define AOC_ERR_BUFFER_TOO_SMALL -4
+
+/**
+ * The solver failed on this input.
+ */
+#define AOC_ERR_SOLVER_FAILED -5
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solve `part` of `day` for the `len` bytes of UTF-8 input at `input`.
+ *
+ * On success the answer is written as a NUL-terminated string into
+ * `out_buf`, which has room for `out_len` bytes, and `AOC_OK` is returned.
+ * Otherwise one of the `AOC_ERR_*` codes is returned and `out_buf` is left
+ * untouched.
+ *
+ * # Safety
+ *
+ * `input` must point to `len` readable bytes and `out_buf` to `out_len`
+ * writable bytes.
+ */
+int32_t aoc_solve(uint8_t day,
+                  uint8_t part,
+                  const uint8_t *input,
+                  size_t len,
+                  char *out_buf,
+                  size_t out_len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* AOC_H */
@@ -0,0 +1,181 @@
+//! C ABI for the solvers of all days.
+//!
+//! The C header is `include/aoc.h`, for C and C++. The build script
+//! generates it again with cbindgen into its `OUT_DIR`, and a test fails
+//! when the committed copy is out of date.
+
+use shared::parsers::{parse_lines, try_solve};
+use std::{os::raw::c_char, panic, slice};
+
+/// The answer was written to the output buffer.
+pub const AOC_OK: i32 = 0;
+/// The input or output pointer was null.
+pub const AOC_ERR_NULL_POINTER: i32 = -1;
+/// The input is not valid UTF-8.
+pub const AOC_ERR_INVALID_UTF8: i32 = -2;
+/// There is no solver for this day and part.
+pub const AOC_ERR_UNKNOWN_PUZZLE: i32 = -3;
+/// The answer plus its terminating NUL doesn't fit into the output buffer.
+pub const AOC_ERR_BUFFER_TOO_SMALL: i32 = -4;
+/// The solver failed on this input.
+pub const AOC_ERR_SOLVER_FAILED: i32 = -5;
+
+fn solve(day: u8, part: u8, input: &str) -> Result<String, i32> {
+    let answer = match (day, part) {
+        (1, 1) => d01::part_1(input.lines())
+            .map_err(|_| AOC_ERR_SOLVER_FAILED)?
+            .to_string(),
+        (1, 2) => d01::part_2(input.lines())
+            .map_err(|_| AOC_ERR_SOLVER_FAILED)?
+            .to_string(),
+        (2, 1) => d02::part_1(input.lines())
+            .map_err(|_| AOC_ERR_SOLVER_FAILED)?
+            .to_string(),
+        (2, 2) => d02::part_2(input.lines())
+            .map_err(|_| AOC_ERR_SOLVER_FAILED)?
+            .to_string(),
+        (3, 1) => d03::part_1(d03::parse_lines(input.lines()))
+            .map_err(|_| AOC_ERR_SOLVER_FAILED)?
+            .to_string(),
+        (3, 2) => d03::part_2(d03::parse_lines(input.lines()))
+            .map_err(|_| AOC_ERR_SOLVER_FAILED)?
+            .to_string(),
+        (4, 1) => try_solve(parse_lines(input, d04::parse_pair), |pairs| {
+            d04::part_1(pairs)
+        })
+        .map_err(|_| AOC_ERR_SOLVER_FAILED)?
+        .to_string(),
+        (4, 2) => try_solve(parse_lines(input, d04::parse_pair), |pairs| {
+            d04::part_2(pairs)
+        })
+        .map_err(|_| AOC_ERR_SOLVER_FAILED)?
+        .to_string(),
+        (5, 1) => {
+            let (stacks, moves) = d05::parse_lines(input.lines());
+            d05::part_1(stacks, moves).into_iter().collect()
+        }
+        (5, 2) => {
+            let (stacks, moves) = d05::parse_lines(input.lines());
+            d05::part_2(stacks, moves).into_iter().collect()
+        }
+        (6, 1) => d06::part_1(input).to_string(),
+        (6, 2) => d06::part_2(input).to_string(),
+        (7, 1) => d07::part_1(input.lines()).to_string(),
+        (7, 2) => d07::part_2(input.lines()).to_string(),
+        (8, 1) => d08::part_1(input).to_string(),
+        (8, 2) => d08::part_2(input).to_string(),
+        _ => return Err(AOC_ERR_UNKNOWN_PUZZLE),
+    };
+    Ok(answer)
+}
+
+/// Solve `part` of `day` for the `len` bytes of UTF-8 input at `input`.
+///
+/// On success the answer is written as a NUL-terminated string into
+/// `out_buf`, which has room for `out_len` bytes, and `AOC_OK` is returned.
+/// Otherwise one of the `AOC_ERR_*` codes is returned and `out_buf` is left
+/// untouched.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes and `out_buf` to `out_len`
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    out_buf: *mut c_char,
+    out_len: usize,
+) -> i32 {
+    if input.is_null() || out_buf.is_null() {
+        return AOC_ERR_NULL_POINTER;
+    }
+
+    let input = match std::str::from_utf8(slice::from_raw_parts(input, len)) {
+        Ok(input) => input,
+        Err(_) => return AOC_ERR_INVALID_UTF8,
+    };
+
+    // the solvers panic on inputs they can't handle, which must not unwind
+    // into the caller.
+    let answer = match panic::catch_unwind(|| solve(day, part, input)) {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(code)) => return code,
+        Err(_) => return AOC_ERR_SOLVER_FAILED,
+    };
+
+    if answer.len() >= out_len {
+        return AOC_ERR_BUFFER_TOO_SMALL;
+    }
+
+    let out = slice::from_raw_parts_mut(out_buf as *mut u8, out_len);
+    out[..answer.len()].copy_from_slice(answer.as_bytes());
+    out[answer.len()] = 0;
+
+    AOC_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn call(day: u8, part: u8, input: &[u8], out_len: usize) -> Result<String, i32> {
+        let mut buf = vec![0 as c_char; out_len];
+        let code = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                buf.as_mut_ptr(),
+                buf.len(),
+            )
+        };
+        if code == AOC_OK {
+            Ok(unsafe { CStr::from_ptr(buf.as_ptr()) }
+                .to_str()
+                .unwrap()
+                .to_string())
+        } else {
+            Err(code)
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            call(6, 1, b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", 32),
+            Ok("7".into())
+        );
+        assert_eq!(
+            call(6, 2, b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", 32),
+            Ok("19".into())
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(call(9, 1, b"", 32), Err(AOC_ERR_UNKNOWN_PUZZLE));
+        assert_eq!(call(1, 3, b"", 32), Err(AOC_ERR_UNKNOWN_PUZZLE));
+        assert_eq!(call(1, 1, &[0xff], 32), Err(AOC_ERR_INVALID_UTF8));
+        assert_eq!(call(1, 1, b"1\nx\n", 32), Err(AOC_ERR_SOLVER_FAILED));
+        assert_eq!(call(2, 2, b"A Y\nB Q\n", 32), Err(AOC_ERR_SOLVER_FAILED));
+        assert_eq!(call(3, 2, b"abcd\naefg\n", 32), Err(AOC_ERR_SOLVER_FAILED));
+        assert_eq!(call(3, 1, b"ab!a\n", 32), Err(AOC_ERR_SOLVER_FAILED));
+        assert_eq!(
+            call(4, 1, b"1-2,3-4\n1-2\n", 32),
+            Err(AOC_ERR_SOLVER_FAILED)
+        );
+        assert_eq!(
+            call(6, 2, b"mjqjpqmgbljsphdztnvjfqwrcgsmlb", 2),
+            Err(AOC_ERR_BUFFER_TOO_SMALL)
+        );
+        assert_eq!(
+            unsafe { aoc_solve(1, 1, std::ptr::null(), 0, std::ptr::null_mut(), 0) },
+            AOC_ERR_NULL_POINTER
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Compiles `harness.c` as C and as C++ against the committed header and the
+//! static library, with the system libraries rustc reports for the target,
+//! and runs it.
+#![cfg(any(target_os = "linux", target_os = "macos"))]
+
+use std::{path::PathBuf, process::Command};
+
+/// Build the harness with `compiler` and the extra `flags` before the
+/// source, and run it.
+fn run_harness(compiler: &str, flags: &[&str], name: &str) {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let native_libs = env!(
+        "AOC_FFI_NATIVE_LIBS",
+        "the build script could not find the native libraries"
+    );
+
+    // target/<profile>/deps/<this test> -> target/<profile>
+    let profile_dir = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(|deps| deps.parent())
+        .unwrap()
+        .to_path_buf();
+
+    let binary = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+
+    let status = Command::new(compiler)
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .args(flags)
+        .arg(manifest_dir.join("tests/harness.c"))
+        // the library is no source, whatever the flags said
+        .args(["-x", "none"])
+        .arg(profile_dir.join("libaoc_ffi.a"))
+        .args(native_libs.split_whitespace())
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .unwrap_or_else(|err| panic!("could not run {}: {}", compiler, err));
+    assert!(status.success(), "compiling {} failed", name);
+
+    let output = Command::new(&binary).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{} failed:\n{}",
+        name,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_c_harness() {
+    let cc = std::env::var("CC").unwrap_or_else(|_| "cc".into());
+    run_harness(&cc, &["-x", "c"], "harness");
+}
+
+#[test]
+fn test_cpp_harness() {
+    let cxx = std::env::var("CXX").unwrap_or_else(|_| "c++".into());
+    run_harness(&cxx, &["-x", "c++"], "harness_cpp");
+}
@@ -0,0 +1,90 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void check(uint8_t day, uint8_t part, const char *input, const char *expected) {
+    char out[64];
+    int32_t code = aoc_solve(day, part, (const uint8_t *)input, strlen(input), out, sizeof(out));
+
+    if (code != AOC_OK) {
+        fprintf(stderr, "day %d part %d: error %d\n", day, part, code);
+        failures++;
+    } else if (strcmp(out, expected) != 0) {
+        fprintf(stderr, "day %d part %d: expected %s, got %s\n", day, part, expected, out);
+        failures++;
+    }
+}
+
+static void check_error(uint8_t day, uint8_t part, const char *input, size_t out_len, int32_t expected) {
+    char out[64];
+    int32_t code = aoc_solve(day, part, (const uint8_t *)input, strlen(input), out, out_len);
+
+    if (code != expected) {
+        fprintf(stderr, "day %d part %d: expected error %d, got %d\n", day, part, expected, code);
+        failures++;
+    }
+}
+
+int main(void) {
+    const char *d01 = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+    check(1, 1, d01, "24000");
+    check(1, 2, d01, "45000");
+
+    const char *d02 = "A Y\nB X\nC Z\n";
+    check(2, 1, d02, "15");
+    check(2, 2, d02, "12");
+
+    const char *d03 = "vJrwpWtwJgWrhcsFMMfFFhFp\n"
+                      "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n"
+                      "PmmdzqPrVvPwwTWBwg\n"
+                      "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n"
+                      "ttgJtRGJQctTZtZT\n"
+                      "CrZsJsPPZsGzwwsLwLmpwMDw\n";
+    check(3, 1, d03, "157");
+    check(3, 2, d03, "70");
+
+    const char *d04 = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+    check(4, 1, d04, "2");
+    check(4, 2, d04, "4");
+
+    const char *d05 = "    [D]    \n"
+                      "[N] [C]    \n"
+                      "[Z] [M] [P]\n"
+                      " 1   2   3 \n"
+                      "\n"
+                      "move 1 from 2 to 1\n"
+                      "move 3 from 1 to 3\n"
+                      "move 2 from 2 to 1\n"
+                      "move 1 from 1 to 2\n";
+    check(5, 1, d05, "CMZ");
+    check(5, 2, d05, "MCD");
+
+    const char *d06 = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    check(6, 1, d06, "7");
+    check(6, 2, d06, "19");
+
+    const char *d07 = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n"
+                      "$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n"
+                      "$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n"
+                      "$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k\n";
+    check(7, 1, d07, "95437");
+    check(7, 2, d07, "24933642");
+
+    const char *d08 = "30373\n25512\n65332\n33549\n35390\n";
+    check(8, 1, d08, "21");
+    check(8, 2, d08, "8");
+
+    check_error(26, 1, "", 64, AOC_ERR_UNKNOWN_PUZZLE);
+    check_error(6, 2, d06, 2, AOC_ERR_BUFFER_TOO_SMALL);
+    check_error(1, 1, "\xff", 64, AOC_ERR_INVALID_UTF8);
+    check_error(1, 1, "1000\nabc\n", 64, AOC_ERR_SOLVER_FAILED);
+    if (aoc_solve(1, 1, NULL, 0, NULL, 0) != AOC_ERR_NULL_POINTER) {
+        fprintf(stderr, "null pointers not detected\n");
+        failures++;
+    }
+
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,18 @@
+//! The header in `include/` is the one users of the library get, so it has
+//! to match the one the build script generates from the current sources.
+
+use std::path::Path;
+
+#[test]
+fn test_header_is_current() {
+    let generated = Path::new(env!("AOC_FFI_INCLUDE_DIR")).join("aoc.h");
+    let committed = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/aoc.h");
+
+    assert!(
+        std::fs::read_to_string(&generated).unwrap()
+            == std::fs::read_to_string(&committed).unwrap_or_default(),
+        "{} is out of date, copy {} over it",
+        committed.display(),
+        generated.display()
+    );
+}
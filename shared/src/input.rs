@@ -0,0 +1,87 @@
+use memmap2::Mmap;
+use std::{fs::File, io, path::Path, str::Utf8Error};
+
+/// Read the input file given as the first command line argument.
+///
+/// The days borrow their lines straight from the returned buffer, so the
+/// input is only allocated once.
+pub fn load() -> String {
+    let path = std::env::args()
+        .nth(1)
+        .expect("missing input file argument");
+    std::fs::read_to_string(path).expect("could not read input file")
+}
+
+/// A read-only memory mapping of an input file, for inputs too large to
+/// comfortably read into memory.
+pub struct Mapped {
+    mmap: Option<Mmap>,
+}
+
+impl Mapped {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+
+        // mapping an empty file fails on some platforms
+        if file.metadata()?.len() == 0 {
+            return Ok(Mapped { mmap: None });
+        }
+
+        // SAFETY: the mapping is only valid as long as nobody truncates or
+        // modifies the file while we're reading it, which we can't prevent
+        // but is fine for puzzle inputs.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(Mapped { mmap: Some(mmap) })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.mmap.as_deref().unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        std::str::from_utf8(self.as_bytes())
+    }
+}
+
+/// Memory-map the input file given as the first command line argument.
+pub fn map() -> Mapped {
+    let path = std::env::args()
+        .nth(1)
+        .expect("missing input file argument");
+    Mapped::open(path).expect("could not map input file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_mapped() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"1000\n2000\n").unwrap();
+
+        let mapped = Mapped::open(file.path()).unwrap();
+        assert_eq!(mapped.as_bytes(), b"1000\n2000\n");
+        assert_eq!(mapped.as_str().unwrap(), "1000\n2000\n");
+    }
+
+    #[test]
+    fn test_mapped_empty() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+
+        let mapped = Mapped::open(file.path()).unwrap();
+        assert_eq!(mapped.as_bytes(), b"");
+        assert_eq!(mapped.as_str().unwrap(), "");
+    }
+
+    #[test]
+    fn test_mapped_invalid_utf8() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[b'1', 0xff, b'\n']).unwrap();
+
+        let mapped = Mapped::open(file.path()).unwrap();
+        assert_eq!(mapped.as_bytes(), &[b'1', 0xff, b'\n']);
+        assert!(mapped.as_str().is_err());
+    }
+}
@@ -1 +1,3 @@
+pub mod input;
 pub mod parsers;
+pub mod store;
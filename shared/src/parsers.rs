@@ -1,6 +1,7 @@
 use nom::{
     character::complete::multispace0, error::ParseError, multi::many1, sequence::delimited, Parser,
 };
+use std::fmt;
 
 pub fn parse_input<'i, O, E, P>(input: &'i str, parser: P) -> Vec<O>
 where
@@ -12,3 +13,110 @@ where
     debug_assert!(remainder.is_empty());
     result
 }
+
+/// A line [`parse_lines`] couldn't parse, `line` starts at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    pub line: usize,
+    pub content: String,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: cannot parse {:?}", self.line, self.content)
+    }
+}
+
+impl std::error::Error for LineError {}
+
+/// Like `parse_input`, but lazily parses one line at a time instead of
+/// collecting everything, so huge inputs never have to fit into memory twice.
+///
+/// Blank lines are skipped, and a line the parser fails on or doesn't
+/// consume completely is an error.
+pub fn parse_lines<'i, O, E, P>(
+    input: &'i str,
+    mut parser: P,
+) -> impl Iterator<Item = Result<O, LineError>> + 'i
+where
+    P: Parser<&'i str, O, E> + 'i,
+    E: ParseError<&'i str> + 'i,
+    O: 'i,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(move |(number, line)| match parser.parse(line) {
+            Ok(("", result)) => Ok(result),
+            _ => Err(LineError {
+                line: number,
+                content: line.to_string(),
+            }),
+        })
+}
+
+/// Run `solve` on the values of `results` up to the first error, which is
+/// returned instead of the answer.
+pub fn try_solve<T, E, R>(
+    results: impl Iterator<Item = Result<T, E>>,
+    solve: impl FnOnce(&mut dyn Iterator<Item = T>) -> R,
+) -> Result<R, E> {
+    let mut error = None;
+    let answer =
+        solve(&mut results.map_while(|result| result.map_err(|err| error = Some(err)).ok()));
+
+    match error {
+        Some(err) => Err(err),
+        None => Ok(answer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{character::complete::u64, error::Error};
+
+    fn numbers(input: &str) -> Vec<Result<u64, LineError>> {
+        parse_lines(input, u64::<&str, Error<&str>>).collect()
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(numbers("1\n  2 \n\n3"), [Ok(1), Ok(2), Ok(3)]);
+        assert_eq!(numbers(""), []);
+    }
+
+    #[test]
+    fn test_parse_lines_errors() {
+        let error = |line, content: &str| {
+            Err(LineError {
+                line,
+                content: content.into(),
+            })
+        };
+
+        assert_eq!(numbers("1\n\nx\n4"), [Ok(1), error(3, "x"), Ok(4)]);
+        // the whole line has to be parsed
+        assert_eq!(numbers("12ab"), [error(1, "12ab")]);
+        assert_eq!(
+            numbers("1\nx").remove(1).unwrap_err().to_string(),
+            "line 2: cannot parse \"x\""
+        );
+    }
+
+    #[test]
+    fn test_try_solve() {
+        let sum = |values: &mut dyn Iterator<Item = u64>| values.sum::<u64>();
+
+        assert_eq!(try_solve(numbers("1\n2").into_iter(), sum), Ok(3));
+        assert_eq!(
+            try_solve(numbers("1\nx\n2").into_iter(), sum),
+            Err(LineError {
+                line: 2,
+                content: "x".into()
+            })
+        );
+    }
+}
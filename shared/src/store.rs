@@ -0,0 +1,346 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Key {
+    pub year: u16,
+    pub day: u8,
+    pub account: String,
+}
+
+impl Key {
+    pub fn new(year: u16, day: u8, account: impl Into<String>) -> Self {
+        Key {
+            year,
+            day,
+            account: account.into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Fetch(String),
+    NotCached(Key),
+    /// the account can't name a directory of the store
+    InvalidAccount(String),
+    /// the fetcher downloads the inputs of another account than the key's
+    WrongAccount {
+        key: Key,
+        fetcher: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "io error: {}", err),
+            Error::Fetch(msg) => write!(f, "could not fetch input: {}", msg),
+            Error::NotCached(key) => write!(
+                f,
+                "input for {} day {} ({}) is not cached",
+                key.year, key.day, key.account
+            ),
+            Error::InvalidAccount(account) => write!(f, "invalid account name {:?}", account),
+            Error::WrongAccount { key, fetcher } => write!(
+                f,
+                "cannot fetch the input of {} with the credentials of {}",
+                key.account, fetcher
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// Something that can download a puzzle input. The account is fixed by the
+/// fetcher (it's whoever the credentials belong to).
+pub trait Fetcher {
+    /// the account the inputs are downloaded for
+    fn account(&self) -> &str;
+
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Error>;
+}
+
+/// Puzzle inputs cached on disk as `<dir>/<account>/<year>/dNN.txt`.
+#[derive(Debug, Clone)]
+pub struct InputStore {
+    dir: PathBuf,
+}
+
+impl InputStore {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        InputStore {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// The account is a single directory, so it can't be empty, `.`, `..`
+    /// or contain path separators.
+    pub fn path(&self, key: &Key) -> Result<PathBuf, Error> {
+        let account = key.account.as_str();
+        if matches!(account, "" | "." | "..") || account.contains(std::path::is_separator) {
+            return Err(Error::InvalidAccount(key.account.clone()));
+        }
+
+        Ok(self
+            .dir
+            .join(account)
+            .join(key.year.to_string())
+            .join(format!("d{:02}.txt", key.day)))
+    }
+
+    /// read a cached input, `None` when we don't have it yet.
+    pub fn cached(&self, key: &Key) -> Result<Option<String>, Error> {
+        match std::fs::read_to_string(self.path(key)?) {
+            Ok(input) => Ok(Some(input)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn store(&self, key: &Key, input: &str) -> Result<(), Error> {
+        let path = self.path(key)?;
+        std::fs::create_dir_all(path.parent().expect("path without parent"))?;
+
+        // write + rename so an interrupted download never looks cached.
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, input)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// read a cached input, or use the fetcher to download and cache it.
+    /// Without a fetcher a missing input is an error, and so is a fetcher
+    /// for another account.
+    pub fn get(&self, key: &Key, fetcher: Option<&dyn Fetcher>) -> Result<String, Error> {
+        if let Some(fetcher) = fetcher.filter(|fetcher| fetcher.account() != key.account) {
+            return Err(Error::WrongAccount {
+                key: key.clone(),
+                fetcher: fetcher.account().to_string(),
+            });
+        }
+
+        if let Some(input) = self.cached(key)? {
+            return Ok(input);
+        }
+
+        let fetcher = fetcher.ok_or_else(|| Error::NotCached(key.clone()))?;
+        let input = fetcher.fetch(key.year, key.day)?;
+        self.store(key, &input)?;
+        Ok(input)
+    }
+}
+
+/// Downloads inputs from the AoC website (or anything that looks like it)
+/// using the session cookie of the account.
+#[cfg(feature = "fetch")]
+pub struct HttpFetcher {
+    base_url: String,
+    session: String,
+    account: String,
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "fetch")]
+impl HttpFetcher {
+    pub fn new(
+        base_url: impl Into<String>,
+        session: impl Into<String>,
+        account: impl Into<String>,
+    ) -> Self {
+        HttpFetcher {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            account: account.into(),
+            agent: ureq::agent(),
+        }
+    }
+}
+
+#[cfg(feature = "fetch")]
+impl Fetcher for HttpFetcher {
+    fn account(&self) -> &str {
+        &self.account
+    }
+
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        self.agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| Error::Fetch(err.to_string()))?
+            .into_string()
+            .map_err(Error::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct CountingFetcher {
+        calls: Cell<usize>,
+    }
+
+    impl Fetcher for CountingFetcher {
+        fn account(&self) -> &str {
+            "me"
+        }
+
+        fn fetch(&self, year: u16, day: u8) -> Result<String, Error> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(format!("input {} {}\n", year, day))
+        }
+    }
+
+    #[test]
+    fn test_path() {
+        let store = InputStore::new("/cache");
+        assert_eq!(
+            store.path(&Key::new(2022, 1, "me")).unwrap(),
+            Path::new("/cache/me/2022/d01.txt")
+        );
+    }
+
+    #[test]
+    fn test_invalid_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path().join("cache"));
+
+        for account in ["", ".", "..", "../me", "me/..", "a/b", "/etc"] {
+            let key = Key::new(2022, 1, account);
+            assert!(
+                matches!(store.path(&key), Err(Error::InvalidAccount(_))),
+                "{:?}",
+                account
+            );
+            assert!(matches!(
+                store.store(&key, "input"),
+                Err(Error::InvalidAccount(_))
+            ));
+        }
+        assert!(!dir.path().join("cache").exists());
+        assert!(store.path(&Key::new(2022, 1, "me..too")).is_ok());
+    }
+
+    #[test]
+    fn test_wrong_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        let fetcher = CountingFetcher {
+            calls: Cell::new(0),
+        };
+
+        assert_eq!(
+            store
+                .get(&Key::new(2022, 1, "other"), Some(&fetcher))
+                .unwrap_err()
+                .to_string(),
+            "cannot fetch the input of other with the credentials of me"
+        );
+        assert_eq!(fetcher.calls.get(), 0);
+    }
+
+    #[test]
+    fn test_not_cached_without_fetcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+
+        assert!(matches!(
+            store.get(&Key::new(2022, 1, "me"), None),
+            Err(Error::NotCached(_))
+        ));
+    }
+
+    #[test]
+    fn test_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        let key = Key::new(2022, 1, "me");
+
+        store.store(&key, "1000\n2000\n").unwrap();
+
+        assert_eq!(store.get(&key, None).unwrap(), "1000\n2000\n");
+        assert_eq!(store.cached(&Key::new(2022, 1, "other")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_fetch_only_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        let fetcher = CountingFetcher {
+            calls: Cell::new(0),
+        };
+        let key = Key::new(2022, 3, "me");
+
+        assert_eq!(store.get(&key, Some(&fetcher)).unwrap(), "input 2022 3\n");
+        assert_eq!(store.get(&key, Some(&fetcher)).unwrap(), "input 2022 3\n");
+        assert_eq!(fetcher.calls.get(), 1);
+        assert!(store.path(&key).unwrap().exists());
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn test_http_fetcher() {
+        use std::{
+            io::{BufRead, BufReader, Write},
+            net::TcpListener,
+            thread,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let body = "1000\n2000\n";
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = InputStore::new(dir.path());
+        let fetcher = HttpFetcher::new(base_url, "secret", "me");
+        let key = Key::new(2022, 1, "me");
+
+        assert_eq!(store.get(&key, Some(&fetcher)).unwrap(), "1000\n2000\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=secret\r\n"));
+
+        // the server is gone, so this can only work from the cache
+        assert_eq!(store.get(&key, Some(&fetcher)).unwrap(), "1000\n2000\n");
+    }
+}